@@ -0,0 +1,197 @@
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::session_index::STATE_SUBDIR;
+use crate::session_store::SessionSummary;
+
+const ANNOTATIONS_FILE: &str = "annotations.json";
+
+/// User-supplied labels for one session, kept out of the rollout so Codex never sees them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Annotations {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.note.is_none() && !self.pinned && self.title.is_none()
+    }
+}
+
+/// Sidecar file of annotations keyed by session id.
+pub struct AnnotationStore {
+    path: PathBuf,
+    sessions: BTreeMap<String, Annotations>,
+}
+
+impl AnnotationStore {
+    /// Load the store, starting empty if it does not exist yet.
+    pub fn load(codex_home: &Path) -> Result<Self> {
+        let path = annotations_path(codex_home);
+        let sessions = if path.exists() {
+            let file = File::open(&path).with_context(|| format!("failed to open {path:?}"))?;
+            serde_json::from_reader(BufReader::new(file))
+                .with_context(|| format!("invalid annotations file {path:?}"))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, sessions })
+    }
+
+    /// Load for reading only. A broken file is reported once and treated as empty, so
+    /// listing keeps working; commands that write annotations still fail on it.
+    pub fn load_or_empty(codex_home: &Path) -> Self {
+        static WARNED: AtomicBool = AtomicBool::new(false);
+        Self::load(codex_home).unwrap_or_else(|err| {
+            if !WARNED.swap(true, Ordering::Relaxed) {
+                eprintln!(
+                    "{} {err:#}; ignoring tags, titles, pins and notes",
+                    "Warning:".yellow()
+                );
+            }
+            Self {
+                path: annotations_path(codex_home),
+                sessions: BTreeMap::new(),
+            }
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Annotations> {
+        self.sessions.get(id)
+    }
+
+    /// Copy the stored annotations onto a summary.
+    pub fn apply(&self, summary: &mut SessionSummary) {
+        if let Some(annotations) = self.get(&summary.id) {
+            summary.tags = annotations.tags.clone();
+            summary.pinned = annotations.pinned;
+            summary.title = annotations.title.clone();
+        }
+    }
+
+    /// Add tags to a session, ignoring ones it already has; returns the tags actually added.
+    pub fn add_tags(&mut self, id: &str, tags: &[String]) -> Vec<String> {
+        let entry = self.sessions.entry(id.to_string()).or_default();
+        let mut added = Vec::new();
+        for tag in tags
+            .iter()
+            .map(|tag| tag.trim())
+            .filter(|tag| !tag.is_empty())
+        {
+            if !entry.tags.iter().any(|existing| existing == tag) {
+                entry.tags.push(tag.to_string());
+                added.push(tag.to_string());
+            }
+        }
+        entry.tags.sort();
+        added
+    }
+
+    /// Remove tags from a session; returns the tags that were actually present.
+    pub fn remove_tags(&mut self, id: &str, tags: &[String]) -> Vec<String> {
+        let Some(entry) = self.sessions.get_mut(id) else {
+            return Vec::new();
+        };
+        let mut removed = Vec::new();
+        entry.tags.retain(|existing| {
+            let doomed = tags.iter().any(|tag| tag.trim() == existing);
+            if doomed {
+                removed.push(existing.clone());
+            }
+            !doomed
+        });
+        self.prune(id);
+        removed
+    }
+
+    /// Replace the note on a session; `None` clears it.
+    pub fn set_note(&mut self, id: &str, note: Option<String>) {
+        let note = note.filter(|text| !text.trim().is_empty());
+        self.sessions.entry(id.to_string()).or_default().note = note;
+        self.prune(id);
+    }
+
+    /// Replace the title of a session; `None` or blank text clears it.
+    pub fn set_title(&mut self, id: &str, title: Option<String>) {
+        let title = title
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        self.sessions.entry(id.to_string()).or_default().title = title;
+        self.prune(id);
+    }
+
+    /// Pin or unpin a session; returns false when it was already in that state.
+    pub fn set_pinned(&mut self, id: &str, pinned: bool) -> bool {
+        let entry = self.sessions.entry(id.to_string()).or_default();
+        let changed = entry.pinned != pinned;
+        entry.pinned = pinned;
+        self.prune(id);
+        changed
+    }
+
+    /// Ids of every pinned session.
+    pub fn pinned_ids(&self) -> HashSet<String> {
+        self.sessions
+            .iter()
+            .filter(|(_, annotations)| annotations.pinned)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Every tag in use with the number of sessions carrying it, most used first.
+    pub fn tag_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for annotations in self.sessions.values() {
+            for tag in &annotations.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("unable to create directory {parent:?}"))?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let mut writer = BufWriter::new(
+                File::create(&tmp_path)
+                    .with_context(|| format!("failed to create {tmp_path:?}"))?,
+            );
+            serde_json::to_writer_pretty(&mut writer, &self.sessions)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("failed to write annotations file {:?}", self.path))
+    }
+
+    fn prune(&mut self, id: &str) {
+        if self.sessions.get(id).is_some_and(Annotations::is_empty) {
+            self.sessions.remove(id);
+        }
+    }
+}
+
+pub fn annotations_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(STATE_SUBDIR).join(ANNOTATIONS_FILE)
+}
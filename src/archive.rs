@@ -0,0 +1,55 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::session_store::{ARCHIVED_SUBDIR, SESSIONS_SUBDIR};
+
+/// Move a rollout into the archive directory, keeping its `YYYY/MM/DD` layout.
+pub fn archive_session(codex_home: &Path, path: &Path) -> Result<PathBuf> {
+    relocate(
+        path,
+        &codex_home.join(SESSIONS_SUBDIR),
+        &codex_home.join(ARCHIVED_SUBDIR),
+    )
+}
+
+/// Move an archived rollout back to where Codex looks for resumable sessions.
+pub fn unarchive_session(codex_home: &Path, path: &Path) -> Result<PathBuf> {
+    relocate(
+        path,
+        &codex_home.join(ARCHIVED_SUBDIR),
+        &codex_home.join(SESSIONS_SUBDIR),
+    )
+}
+
+fn relocate(path: &Path, from_root: &Path, to_root: &Path) -> Result<PathBuf> {
+    let source = fs::canonicalize(path).with_context(|| format!("failed to resolve {path:?}"))?;
+    let from_root = fs::canonicalize(from_root).unwrap_or_else(|_| from_root.to_path_buf());
+    let Ok(relative) = source.strip_prefix(&from_root) else {
+        bail!("{} is not under {}", path.display(), from_root.display());
+    };
+
+    let target = to_root.join(relative);
+    if target.exists() {
+        bail!("{} already exists", target.display());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory {parent:?}"))?;
+    }
+    fs::rename(&source, &target)
+        .with_context(|| format!("failed to move {source:?} to {target:?}"))?;
+    remove_empty_parents(&source, &from_root);
+    Ok(target)
+}
+
+/// Drop the day/month/year directories a move left empty, stopping at `root`.
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if current == root || !current.starts_with(root) || fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeSet;
+
+const MAX_TITLE_CHARS: usize = 60;
+const MAX_LISTED: usize = 3;
+/// Replies that steer an ongoing session but say nothing about what it is for.
+const FILLER_REPLIES: &[&str] = &[
+    "continue",
+    "go on",
+    "go ahead",
+    "proceed",
+    "yes",
+    "y",
+    "no",
+    "ok",
+    "okay",
+    "sure",
+    "thanks",
+    "thank you",
+    "lgtm",
+    "do it",
+    "next",
+    "retry",
+    "try again",
+];
+const PATCH_MARKERS: &[&str] = &["*** Update File: ", "*** Add File: ", "*** Delete File: "];
+const SHELL_WRAPPERS: &[&str] = &["bash", "sh", "zsh", "-lc", "-c", "sudo", "env"];
+
+/// Collects clues from a rollout and turns them into a short label, without any network calls.
+///
+/// The first user request that says something is preferred; otherwise the label
+/// falls back to the files a patch touched, then to the commands that were run.
+#[derive(Debug, Default)]
+pub struct TitleHints {
+    request: Option<String>,
+    filler: Option<String>,
+    files: BTreeSet<String>,
+    commands: Vec<String>,
+}
+
+impl TitleHints {
+    pub fn observe_user_message(&mut self, text: &str) {
+        if self.request.is_some() {
+            return;
+        }
+        let Some(line) = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('<') && !line.starts_with('#'))
+        else {
+            return;
+        };
+        if is_filler(line) {
+            self.filler.get_or_insert_with(|| line.to_string());
+        } else {
+            self.request = Some(line.to_string());
+        }
+    }
+
+    pub fn observe_tool_call(&mut self, name: &str, input: &str) {
+        for line in input.lines() {
+            if let Some(path) = PATCH_MARKERS
+                .iter()
+                .find_map(|marker| line.trim().strip_prefix(marker))
+            {
+                self.files.insert(file_name(path.trim()).to_string());
+            }
+        }
+        if matches!(name, "shell" | "local_shell" | "exec_command")
+            && let Some(program) = program_name(input)
+            && program != "apply_patch"
+            && !self.commands.iter().any(|seen| seen == program)
+        {
+            self.commands.push(program.to_string());
+        }
+    }
+
+    pub fn title(self) -> Option<String> {
+        if let Some(request) = self.request {
+            return Some(shorten(&request));
+        }
+        if !self.files.is_empty() {
+            return Some(shorten(&format!(
+                "Edited {}",
+                list_some(self.files.into_iter().collect())
+            )));
+        }
+        if !self.commands.is_empty() {
+            return Some(shorten(&format!("Ran {}", list_some(self.commands))));
+        }
+        self.filler.map(|text| shorten(&text))
+    }
+}
+
+fn is_filler(line: &str) -> bool {
+    let normalized = line
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_ascii_lowercase();
+    FILLER_REPLIES.contains(&normalized.as_str()) || normalized.split_whitespace().count() < 2
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// First real program in a shell command line, skipping wrappers like `bash -lc`.
+fn program_name(command: &str) -> Option<&str> {
+    command
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c == '\'' || c == '"'))
+        .find(|word| !word.is_empty() && !SHELL_WRAPPERS.contains(word) && !word.contains('='))
+        .map(file_name)
+}
+
+fn list_some(items: Vec<String>) -> String {
+    let extra = items.len().saturating_sub(MAX_LISTED);
+    let mut listed = items
+        .into_iter()
+        .take(MAX_LISTED)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if extra > 0 {
+        listed.push_str(&format!(" (+{extra})"));
+    }
+    listed
+}
+
+fn shorten(text: &str) -> String {
+    if text.chars().count() <= MAX_TITLE_CHARS {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(MAX_TITLE_CHARS).collect();
+        format!("{}…", truncated.trim_end())
+    }
+}
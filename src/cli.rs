@@ -1,6 +1,12 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
 use clap::{ArgAction, Args, Parser, Subcommand};
 use std::path::PathBuf;
 
+use crate::columns::Column;
+use crate::launcher;
+use crate::output::Format;
+use crate::session_store::{EntryKind, SortKey, SourceKind};
+
 #[derive(Debug, Parser)]
 #[command(
     author,
@@ -13,14 +19,27 @@ pub struct Cli {
     #[arg(long = "codex-home", value_name = "DIR", global = true)]
     pub codex_home: Option<PathBuf>,
 
-    /// Path to the codex binary to run when resuming a session.
+    /// Path to the codex binary to run when resuming a session (defaults to the config file, then `codex`).
     #[arg(
         long = "codex-bin",
         value_name = "PATH",
-        default_value = "codex",
+        env = "CODEX_SESSION_CODEX_BIN",
+        global = true
+    )]
+    pub codex_bin: Option<String>,
+
+    /// Read settings from this file instead of `$CODEX_HOME/codex-session.toml`.
+    #[arg(
+        long = "config",
+        value_name = "FILE",
+        env = crate::config::CONFIG_ENV,
         global = true
     )]
-    pub codex_bin: String,
+    pub config: Option<PathBuf>,
+
+    /// Output format of subcommands: table, json, jsonl or tsv.
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = Format::Table, global = true)]
+    pub format: Format,
 
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -38,23 +57,81 @@ pub enum Command {
     /// Show details about a session.
     Info(InfoArgs),
 
-    /// Delete a recorded session.
+    /// Search the full transcripts of recorded sessions.
+    Search(SearchArgs),
+
+    /// Summarise usage across recorded sessions.
+    Stats(StatsArgs),
+
+    /// Export a session transcript; the format follows the file extension.
+    Export(ExportArgs),
+
+    /// Move sessions into the archive so they no longer show up in listings.
+    Archive(ArchiveArgs),
+
+    /// Restore archived sessions so they can be resumed again.
+    Unarchive(ArchiveArgs),
+
+    /// Move a recorded session to the trash.
     Delete(DeleteArgs),
+
+    /// Inspect, restore or empty the trash.
+    Trash(TrashArgs),
+
+    /// Remove or archive every session matching a retention policy.
+    Prune(PruneArgs),
+
+    /// Add, remove or list session tags.
+    Tag(TagArgs),
+
+    /// Show or set the free-form note attached to a session.
+    Note(NoteArgs),
+
+    /// Give a session a human title shown instead of its first message.
+    Rename(RenameArgs),
+
+    /// Keep sessions at the top of every listing.
+    Pin(ArchiveArgs),
+
+    /// Stop keeping sessions at the top of listings.
+    Unpin(ArchiveArgs),
+
+    /// Manage the cached session index.
+    Index(IndexArgs),
+
+    /// Show, locate or edit the configuration file.
+    Config(ConfigArgs),
 }
 
-#[derive(Debug, Args, Clone)]
-pub struct ListArgs {
+/// Which project directories a command covers; defaults to the current repository.
+#[derive(Debug, Args, Clone, Default)]
+pub struct ScopeArgs {
     /// Include sessions from every project directory.
     #[arg(long, short = 'a', default_value_t = false)]
     pub all: bool,
 
-    /// Restrict the listing to sessions recorded under this directory.
+    /// Only include sessions recorded in this directory, or in directories matching this
+    /// glob pattern (e.g. `~/work/*/api`; `**` crosses directory levels).
     #[arg(long = "cwd", value_name = "DIR")]
     pub cwd: Option<PathBuf>,
 
-    /// Maximum number of sessions to display.
-    #[arg(long, default_value_t = 20)]
-    pub limit: usize,
+    /// With `--cwd`, also include sessions recorded in its subdirectories.
+    #[arg(long, default_value_t = false, requires = "cwd")]
+    pub cwd_recursive: bool,
+
+    /// With `--cwd`, also include the same directory in every other worktree of its repository.
+    #[arg(long, default_value_t = false, requires = "cwd")]
+    pub worktrees: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+
+    /// Maximum number of sessions to display (defaults to the config file, then 20).
+    #[arg(long)]
+    pub limit: Option<usize>,
 
     /// Pagination cursor token returned by a previous invocation.
     #[arg(long, value_name = "TOKEN")]
@@ -64,7 +141,49 @@ pub struct ListArgs {
     #[arg(long = "provider", value_name = "PROVIDER", value_delimiter = ',', action = ArgAction::Append)]
     pub providers: Vec<String>,
 
-    /// Emit machine-readable JSON instead of a table.
+    /// Order sessions by this key
+    /// (updated, created, cwd, branch, size, turns, tokens; defaults to the config file).
+    /// Keys other than updated and created sort every matching session before --limit
+    /// applies, and cannot be combined with --cursor.
+    #[arg(long, value_name = "KEY")]
+    pub sort: Option<SortKey>,
+
+    /// Columns to show, comma separated (id, updated, created, duration, branch, commit, repo,
+    /// git, cwd, provider, model, source, messages, size, tokens, tags, conversation;
+    /// defaults to the config file).
+    #[arg(long = "columns", value_name = "COLUMNS", value_delimiter = ',')]
+    pub columns: Vec<Column>,
+
+    /// List archived sessions instead of live ones.
+    #[arg(long, default_value_t = false)]
+    pub archived: bool,
+
+    /// Only list sessions carrying all of these tags (comma separated list).
+    #[arg(long = "tag", value_name = "TAG", value_delimiter = ',', action = ArgAction::Append)]
+    pub tags: Vec<String>,
+
+    /// Only list sessions started by these sources: cli, vscode, exec, mcp, unknown
+    /// (comma separated list; defaults to all of them).
+    #[arg(long = "source", value_name = "SOURCE", value_delimiter = ',', action = ArgAction::Append)]
+    pub sources: Vec<SourceKind>,
+
+    /// Also list empty sessions, which never received a user message.
+    #[arg(long, default_value_t = false)]
+    pub include_empty: bool,
+
+    /// Only list sessions whose repository URL contains this text, e.g. `openai/codex`.
+    #[arg(long, value_name = "REPO")]
+    pub repo: Option<String>,
+
+    /// Only list sessions recorded on this branch.
+    #[arg(long, value_name = "BRANCH")]
+    pub branch: Option<String>,
+
+    /// Only list sessions started at a commit with this hash prefix.
+    #[arg(long, value_name = "HASH")]
+    pub commit: Option<String>,
+
+    /// Shorthand for `--format json`.
     #[arg(long, default_value_t = false)]
     pub json: bool,
 }
@@ -72,11 +191,19 @@ pub struct ListArgs {
 impl Default for ListArgs {
     fn default() -> Self {
         Self {
-            all: false,
-            cwd: None,
-            limit: 20,
+            scope: ScopeArgs::default(),
+            limit: None,
             cursor: None,
             providers: Vec::new(),
+            sort: None,
+            columns: Vec::new(),
+            archived: false,
+            tags: Vec::new(),
+            sources: Vec::new(),
+            include_empty: false,
+            repo: None,
+            branch: None,
+            commit: None,
             json: false,
         }
     }
@@ -92,21 +219,77 @@ pub struct ResumeArgs {
     #[arg(long, default_value_t = false)]
     pub last: bool,
 
-    /// Include sessions from every project directory when prompting.
-    #[arg(long, default_value_t = false)]
-    pub all: bool,
+    #[command(flatten)]
+    pub scope: ScopeArgs,
 
-    /// Restrict prompting to sessions recorded under this directory.
-    #[arg(long = "cwd", value_name = "DIR")]
-    pub cwd: Option<PathBuf>,
+    /// Show at most this many sessions in the picker (defaults to the config file, then 25).
+    #[arg(long)]
+    pub limit: Option<usize>,
 
-    /// Show at most this many sessions in the picker.
-    #[arg(long, default_value_t = 25)]
-    pub limit: usize,
+    /// Only offer sessions started by these sources in the picker and for --last
+    /// (comma separated list; defaults to cli,vscode).
+    #[arg(long = "source", value_name = "SOURCE", value_delimiter = ',', action = ArgAction::Append)]
+    pub sources: Vec<SourceKind>,
 
     /// Print the command but do not execute it.
     #[arg(long, default_value_t = false)]
     pub dry_run: bool,
+
+    /// Resume with the model, reasoning effort, approval and sandbox policy the session last used.
+    #[arg(long, default_value_t = false)]
+    pub match_original: bool,
+
+    /// Set an environment variable for Codex (repeatable).
+    #[arg(long = "env", short = 'e', value_name = "NAME=VALUE", value_parser = parse_env)]
+    pub env: Vec<(String, String)>,
+
+    /// Extra arguments for `codex resume`, after `--`, e.g. `-- --model o3`.
+    #[arg(last = true, value_name = "CODEX_ARGS")]
+    pub codex_args: Vec<String>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct SearchArgs {
+    /// Text to look for in messages, tool calls and command output (case-insensitive).
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+
+    /// Maximum number of matching sessions to display.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Maximum number of snippets to show per session.
+    #[arg(long, default_value_t = 3)]
+    pub snippets: usize,
+
+    /// Shorthand for `--format json`.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct StatsArgs {
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+
+    /// Only count sessions started at or after this time (YYYY-MM-DD, RFC 3339 or an age like 30d).
+    #[arg(long, value_name = "WHEN", value_parser = parse_time_bound)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only count sessions started before this time (YYYY-MM-DD, RFC 3339 or an age like 30d).
+    #[arg(long, value_name = "WHEN", value_parser = parse_time_bound)]
+    pub until: Option<DateTime<Utc>>,
+
+    /// Number of rows to show in each breakdown table.
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Shorthand for `--format json`.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -116,6 +299,33 @@ pub struct InfoArgs {
     pub session: String,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct ExportArgs {
+    /// Session id or path to export.
+    #[arg(value_name = "SESSION_ID_OR_PATH")]
+    pub session: String,
+
+    /// Destination file (.jsonl, .json, .pdf, anything else is Markdown).
+    #[arg(value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// Item kinds to include: message, reasoning, tool_call, tool_output, web_search
+    /// (comma separated; defaults to all of them).
+    #[arg(long = "include", value_name = "KIND", value_delimiter = ',', action = ArgAction::Append)]
+    pub include: Vec<EntryKind>,
+
+    /// Truncate tool output after this many lines (0 keeps everything).
+    #[arg(long, value_name = "LINES", default_value_t = 50)]
+    pub max_output_lines: usize,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ArchiveArgs {
+    /// Session ids or paths.
+    #[arg(value_name = "SESSION_ID_OR_PATH", required = true)]
+    pub sessions: Vec<String>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct DeleteArgs {
     /// Session id or path to delete.
@@ -125,4 +335,326 @@ pub struct DeleteArgs {
     /// Skip the confirmation prompt.
     #[arg(long, short = 'y', default_value_t = false)]
     pub yes: bool,
+
+    /// Remove the rollout for good instead of moving it to the trash.
+    #[arg(long, default_value_t = false)]
+    pub permanent: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct PruneArgs {
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+
+    /// Only consider sessions from these provider ids (comma separated list).
+    #[arg(long = "provider", value_name = "PROVIDER", value_delimiter = ',', action = ArgAction::Append)]
+    pub providers: Vec<String>,
+
+    /// Only consider sessions started by these sources, e.g. `exec` (comma separated list).
+    #[arg(long = "source", value_name = "SOURCE", value_delimiter = ',', action = ArgAction::Append)]
+    pub sources: Vec<SourceKind>,
+
+    /// Prune sessions last active longer ago than this age (e.g. 30d).
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    pub older_than: Option<Duration>,
+
+    /// Prune rollouts smaller than this size (e.g. 4k, 1M).
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub smaller_than: Option<u64>,
+
+    /// Prune empty sessions: started but never sent a single user message.
+    #[arg(long, default_value_t = false)]
+    pub empty: bool,
+
+    /// Always keep this many of the most recent sessions of each project.
+    #[arg(long, value_name = "N")]
+    pub keep_last: Option<usize>,
+
+    /// Archive matching sessions instead of moving them to the trash.
+    #[arg(long, default_value_t = false, conflicts_with = "permanent")]
+    pub archive: bool,
+
+    /// Remove matching rollouts for good instead of moving them to the trash.
+    #[arg(long, default_value_t = false)]
+    pub permanent: bool,
+
+    /// Only show what would be pruned.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Skip the confirmation prompt.
+    #[arg(long, short = 'y', default_value_t = false)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct TagArgs {
+    #[command(subcommand)]
+    pub command: TagCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum TagCommand {
+    /// Attach tags to a session.
+    Add {
+        /// Session id or path to tag.
+        #[arg(value_name = "SESSION_ID_OR_PATH")]
+        session: String,
+        /// Tags to add.
+        #[arg(value_name = "TAG", required = true)]
+        tags: Vec<String>,
+    },
+    /// Detach tags from a session.
+    Remove {
+        /// Session id or path to untag.
+        #[arg(value_name = "SESSION_ID_OR_PATH")]
+        session: String,
+        /// Tags to remove.
+        #[arg(value_name = "TAG", required = true)]
+        tags: Vec<String>,
+    },
+    /// Show every tag in use and how many sessions carry it.
+    List,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct NoteArgs {
+    /// Session id or path.
+    #[arg(value_name = "SESSION_ID_OR_PATH")]
+    pub session: String,
+
+    /// New note text; omit it to print the current note.
+    #[arg(value_name = "TEXT", conflicts_with = "clear")]
+    pub text: Option<String>,
+
+    /// Remove the note.
+    #[arg(long, default_value_t = false)]
+    pub clear: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct RenameArgs {
+    /// Session id or path to rename.
+    #[arg(value_name = "SESSION_ID_OR_PATH")]
+    pub session: String,
+
+    /// New title.
+    #[arg(
+        value_name = "TITLE",
+        required_unless_present = "clear",
+        conflicts_with = "clear"
+    )]
+    pub title: Option<String>,
+
+    /// Remove the title and show the first user message again.
+    #[arg(long, default_value_t = false)]
+    pub clear: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct TrashArgs {
+    #[command(subcommand)]
+    pub command: TrashCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum TrashCommand {
+    /// Show sessions currently in the trash.
+    List {
+        /// Shorthand for `--format json`.
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Move trashed sessions back to their original location.
+    Restore {
+        /// Session ids to restore.
+        #[arg(value_name = "SESSION_ID", required = true)]
+        sessions: Vec<String>,
+    },
+    /// Permanently delete trashed sessions.
+    Empty {
+        /// Only purge sessions deleted longer ago than this age (e.g. 7d).
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        older_than: Option<Duration>,
+
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y', default_value_t = false)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct IndexArgs {
+    #[command(subcommand)]
+    pub command: IndexCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum IndexCommand {
+    /// Discard the cached index and re-parse every rollout.
+    Rebuild,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum ConfigCommand {
+    /// Print the effective settings, defaults included.
+    Show,
+    /// Print the path of the config file in use.
+    Path,
+    /// Open the config file in `$VISUAL` or `$EDITOR`, creating it if needed.
+    Edit,
+}
+
+/// Parse a relative age such as `90m`, `12h`, `30d`, `2w` or `1y`.
+pub fn parse_age(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("`{value}` is missing a unit (m, h, d, w or y)"))?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("`{value}` does not start with a number"))?;
+    let age = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        "y" => amount.checked_mul(365).and_then(Duration::try_days),
+        _ => {
+            return Err(format!(
+                "unknown unit `{unit}` in `{value}` (use m, h, d, w or y)"
+            ));
+        }
+    };
+    age.ok_or_else(|| format!("`{value}` is too large"))
+}
+
+/// The point in time `age` before `now`, or the earliest representable time for huge ages.
+pub fn age_cutoff(now: DateTime<Utc>, age: Duration) -> DateTime<Utc> {
+    now.checked_sub_signed(age)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// Parse a `NAME=VALUE` environment assignment.
+pub fn parse_env(value: &str) -> Result<(String, String), String> {
+    launcher::parse_assignment(value)
+        .ok_or_else(|| format!("`{value}` is not NAME=VALUE with a valid variable name"))
+}
+
+/// Parse a byte size such as `512`, `4k`, `10MB` or `1G` (binary multiples).
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("`{value}` does not start with a number"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        _ => {
+            return Err(format!(
+                "unknown unit `{unit}` in `{value}` (use b, k, m or g)"
+            ));
+        }
+    };
+    amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("`{value}` is too large"))
+}
+
+/// Parse an absolute date (`2026-09-01`, RFC 3339) or a relative age (`30d`) into a point in time.
+pub fn parse_time_bound(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(age) = parse_age(value) {
+        return Utc::now()
+            .checked_sub_signed(age)
+            .ok_or_else(|| format!("`{value}` is too far in the past"));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| format!("`{value}` is not a date (YYYY-MM-DD), RFC 3339 time or age (30d)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_age_accepts_every_unit() {
+        assert_eq!(parse_age("90m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_age("12h"), Ok(Duration::hours(12)));
+        assert_eq!(parse_age(" 30d "), Ok(Duration::days(30)));
+        assert_eq!(parse_age("2w"), Ok(Duration::weeks(2)));
+        assert_eq!(parse_age("1y"), Ok(Duration::days(365)));
+    }
+
+    #[test]
+    fn parse_age_rejects_missing_numbers_and_units() {
+        assert!(parse_age("").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3x").is_err());
+        assert!(parse_age("-3d").is_err());
+    }
+
+    #[test]
+    fn parse_age_rejects_out_of_range_ages() {
+        assert!(parse_age("99999999999999999999d").is_err());
+        assert!(parse_age("9999999999999999y").is_err());
+        assert!(parse_age("9223372036854775807m").is_err());
+    }
+
+    #[test]
+    fn age_cutoff_clamps_to_the_earliest_time() {
+        let now = Utc::now();
+        assert_eq!(age_cutoff(now, Duration::days(1)), now - Duration::days(1));
+        assert_eq!(age_cutoff(now, Duration::MAX), DateTime::<Utc>::MIN_UTC);
+    }
+
+    #[test]
+    fn parse_size_uses_binary_multiples() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("512b"), Ok(512));
+        assert_eq!(parse_size("4k"), Ok(4096));
+        assert_eq!(parse_size("10MB"), Ok(10 << 20));
+        assert_eq!(parse_size("1GiB"), Ok(1 << 30));
+    }
+
+    #[test]
+    fn parse_size_rejects_bad_input() {
+        assert!(parse_size("").is_err());
+        assert!(parse_size("k").is_err());
+        assert!(parse_size("4t").is_err());
+        assert!(parse_size("18446744073709551615k").is_err());
+    }
+
+    #[test]
+    fn parse_time_bound_accepts_dates_times_and_ages() {
+        assert_eq!(
+            parse_time_bound("2026-09-01T12:00:00Z"),
+            Ok("2026-09-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert!(parse_time_bound("2026-09-01").is_ok());
+        assert!(parse_time_bound("30d").is_ok_and(|bound| bound < Utc::now()));
+        assert!(parse_time_bound("yesterday").is_err());
+        assert!(parse_time_bound("9999999999999999y").is_err());
+    }
 }
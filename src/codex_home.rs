@@ -8,10 +8,10 @@ pub fn resolve(override_dir: Option<PathBuf>) -> Result<PathBuf> {
         return canonicalize_existing(path);
     }
 
-    if let Ok(env_val) = std::env::var("CODEX_HOME") {
-        if !env_val.trim().is_empty() {
-            return canonicalize_existing(PathBuf::from(env_val));
-        }
+    if let Ok(env_val) = std::env::var("CODEX_HOME")
+        && !env_val.trim().is_empty()
+    {
+        return canonicalize_existing(PathBuf::from(env_val));
     }
 
     let mut default =
@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::pricing::PriceTable;
+use crate::session_store::SessionSummary;
+
+/// A column of the session table shared by `list` and the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Id,
+    Updated,
+    Created,
+    Duration,
+    Branch,
+    Commit,
+    Repo,
+    /// Whether the recorded branch and commit still exist in the checkout.
+    Git,
+    Cwd,
+    Provider,
+    Model,
+    Source,
+    Messages,
+    Size,
+    Tokens,
+    Tags,
+    Conversation,
+}
+
+impl Column {
+    pub const ALL: [Column; 17] = [
+        Column::Id,
+        Column::Updated,
+        Column::Created,
+        Column::Duration,
+        Column::Branch,
+        Column::Commit,
+        Column::Repo,
+        Column::Git,
+        Column::Cwd,
+        Column::Provider,
+        Column::Model,
+        Column::Source,
+        Column::Messages,
+        Column::Size,
+        Column::Tokens,
+        Column::Tags,
+        Column::Conversation,
+    ];
+
+    pub const DEFAULT: [Column; 6] = [
+        Column::Updated,
+        Column::Branch,
+        Column::Cwd,
+        Column::Tokens,
+        Column::Tags,
+        Column::Conversation,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Updated => "updated",
+            Column::Created => "created",
+            Column::Duration => "duration",
+            Column::Branch => "branch",
+            Column::Commit => "commit",
+            Column::Repo => "repo",
+            Column::Git => "git",
+            Column::Cwd => "cwd",
+            Column::Provider => "provider",
+            Column::Model => "model",
+            Column::Source => "source",
+            Column::Messages => "messages",
+            Column::Size => "size",
+            Column::Tokens => "tokens",
+            Column::Tags => "tags",
+            Column::Conversation => "conversation",
+        }
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Id => "Session",
+            Column::Updated => "Updated",
+            Column::Created => "Created",
+            Column::Duration => "Duration",
+            Column::Branch => "Branch",
+            Column::Commit => "Commit",
+            Column::Repo => "Repository",
+            Column::Git => "Git",
+            Column::Cwd => "CWD",
+            Column::Provider => "Provider",
+            Column::Model => "Model",
+            Column::Source => "Source",
+            Column::Messages => "Msgs",
+            Column::Size => "Size",
+            Column::Tokens => "Tokens",
+            Column::Tags => "Tags",
+            Column::Conversation => "Conversation",
+        }
+    }
+
+    /// Fixed width in the TUI; `None` lets the column take the remaining space.
+    pub fn width(self) -> Option<u16> {
+        match self {
+            Column::Id => Some(36),
+            Column::Updated | Column::Created => Some(20),
+            Column::Duration | Column::Size => Some(9),
+            Column::Branch => Some(12),
+            Column::Commit => Some(8),
+            Column::Repo => Some(20),
+            Column::Git => Some(13),
+            Column::Cwd => Some(30),
+            Column::Provider => Some(10),
+            Column::Source => Some(7),
+            Column::Model => Some(14),
+            Column::Messages => Some(4),
+            Column::Tokens | Column::Tags => Some(16),
+            Column::Conversation => None,
+        }
+    }
+
+    pub fn cell(self, summary: &SessionSummary, prices: &PriceTable) -> String {
+        match self {
+            Column::Id => summary.id.clone(),
+            Column::Updated => summary
+                .updated_at
+                .map(crate::format_relative)
+                .unwrap_or_else(|| "unknown".into()),
+            Column::Created => summary
+                .created_at
+                .map(crate::format_relative)
+                .unwrap_or_else(|| "unknown".into()),
+            Column::Duration => summary
+                .duration()
+                .map(|duration| crate::format_duration(duration.num_seconds().max(0)))
+                .unwrap_or_else(|| "-".into()),
+            Column::Branch => summary.git_branch.clone().unwrap_or_else(|| "-".into()),
+            Column::Commit => summary
+                .git_commit
+                .as_deref()
+                .map(|hash| hash.chars().take(8).collect())
+                .unwrap_or_else(|| "-".into()),
+            Column::Repo => summary.repo_label().unwrap_or_else(|| "-".into()),
+            Column::Git => summary
+                .git_status
+                .map(|status| status.label().to_string())
+                .unwrap_or_else(|| "-".into()),
+            Column::Cwd => summary
+                .cwd
+                .as_ref()
+                .map(|path| crate::shorten_path(path, 28))
+                .unwrap_or_else(|| "(unknown)".into()),
+            Column::Provider => summary.provider.clone().unwrap_or_else(|| "-".into()),
+            Column::Model => summary.model.clone().unwrap_or_else(|| "-".into()),
+            Column::Source => summary.source.to_string(),
+            Column::Messages => summary.turns.to_string(),
+            Column::Size => crate::format_bytes(summary.size),
+            Column::Tokens => crate::format_usage(summary, prices),
+            Column::Tags => crate::format_tags(&summary.tags),
+            Column::Conversation => {
+                crate::truncate_preview(summary.display_title().unwrap_or("(no user message yet)"))
+            }
+        }
+    }
+
+    /// Unabbreviated value for machine-readable output: full paths, RFC 3339 times, raw numbers.
+    pub fn value(self, summary: &SessionSummary) -> String {
+        let time = |at: Option<chrono::DateTime<chrono::Utc>>| {
+            at.map(|at| at.to_rfc3339()).unwrap_or_default()
+        };
+        match self {
+            Column::Id => summary.id.clone(),
+            Column::Updated => time(summary.updated_at),
+            Column::Created => time(summary.created_at),
+            Column::Duration => summary
+                .duration()
+                .map(|duration| duration.num_seconds().to_string())
+                .unwrap_or_default(),
+            Column::Branch => summary.git_branch.clone().unwrap_or_default(),
+            Column::Commit => summary.git_commit.clone().unwrap_or_default(),
+            Column::Repo => summary.git_repository.clone().unwrap_or_default(),
+            Column::Git => summary
+                .git_status
+                .map(|status| status.label().to_string())
+                .unwrap_or_default(),
+            Column::Cwd => summary
+                .cwd
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            Column::Provider => summary.provider.clone().unwrap_or_default(),
+            Column::Model => summary.model.clone().unwrap_or_default(),
+            Column::Source => summary.source.to_string(),
+            Column::Messages => summary.turns.to_string(),
+            Column::Size => summary.size.to_string(),
+            Column::Tokens => summary
+                .tokens
+                .map(|tokens| tokens.total.to_string())
+                .unwrap_or_default(),
+            Column::Tags => summary.tags.join(","),
+            Column::Conversation => summary.display_title().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized = value.trim().to_ascii_lowercase();
+        match normalized.as_str() {
+            "id" | "session" => Ok(Column::Id),
+            "repository" => Ok(Column::Repo),
+            "msgs" | "turns" => Ok(Column::Messages),
+            "preview" | "title" => Ok(Column::Conversation),
+            _ => Column::ALL
+                .into_iter()
+                .find(|column| column.as_str() == normalized)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown column `{value}` (expected one of: {})",
+                        Column::ALL.map(Column::as_str).join(", ")
+                    )
+                }),
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, value};
+
+use crate::cli::{parse_age, parse_size};
+use crate::columns::Column;
+use crate::keymap::{Action, KeyList};
+use crate::pricing::ModelPrice;
+use crate::prune::PruneCriteria;
+use crate::session_store::{PathRemap, SortKey};
+use crate::theme::{ColorOverrides, Preset};
+
+const CONFIG_FILE: &str = "codex-session.toml";
+/// Environment variable naming an explicit config file, used when `--config` is absent.
+pub const CONFIG_ENV: &str = "CODEX_SESSION_CONFIG";
+pub const DEFAULT_CODEX_BIN: &str = "codex";
+
+/// Written by `config edit` when no config file exists yet.
+const TEMPLATE: &str = r#"# codex-session configuration. Command-line flags and environment variables
+# take precedence over everything set here.
+
+# codex_bin = "codex"
+
+[list]
+# limit = 20
+# scope = "repo"           # "all", or "cwd" to only show sessions started in the current directory
+
+[view]
+# sort = "updated"
+# columns = ["updated", "branch", "cwd", "tokens", "tags", "conversation"]
+# group_by_repo = false
+
+[resume]
+# args = ["--search"]      # extra arguments passed to `codex resume`
+# picker_limit = 25
+
+[resume.env]
+# RUST_LOG = "info"        # environment variables set for `codex resume`
+
+# [resume.projects."~/code/app"]
+# args = ["--profile", "app"]  # added after `resume.args` for sessions under this directory
+# env = { OPENAI_BASE_URL = "http://localhost:8080/v1" }
+
+[export]
+# dir = "~/codex-exports"  # where relative export paths are written
+
+[prune]
+# older_than = "90d"       # applied when `prune` is run without criteria
+# keep_last = 5
+# empty = true             # only sessions that never received a user message
+
+[trash]
+# retention_days = 30
+
+[remap]
+# "~/old/app" = "~/code/app"  # sessions recorded under the old path count as the new one
+
+[tui]
+# theme = "dark"           # or "light"
+
+[tui.colors]
+# accent = "cyan"
+# selected_fg = "black"
+# selected_bg = "cyan"
+
+[tui.keys]
+# delete = "x"
+# up = ["k", "up", "ctrl-p"]
+"#;
+
+/// User preferences loaded from `codex-session.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Where the settings were read from (or would be written to).
+    #[serde(skip)]
+    pub path: PathBuf,
+    /// Codex binary used to resume sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codex_bin: Option<String>,
+    pub list: ListConfig,
+    pub view: ViewConfig,
+    pub resume: ResumeConfig,
+    pub export: ExportConfig,
+    pub prune: PruneConfig,
+    pub trash: TrashConfig,
+    pub tui: TuiConfig,
+    /// Per-model prices in USD per million tokens, merged over the built-in table.
+    pub prices: HashMap<String, ModelPrice>,
+    /// Project directories that moved on disk, from the old location to the new one.
+    pub remap: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl Config {
+    pub fn path_remap(&self) -> PathRemap {
+        PathRemap::new(
+            self.remap
+                .iter()
+                .map(|(from, to)| (expand_home(from), expand_home(to))),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Sessions from every project directory.
+    All,
+    /// Sessions recorded anywhere in the current git repository, including its worktrees.
+    #[default]
+    Repo,
+    /// Only sessions recorded in the current directory.
+    Cwd,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListConfig {
+    /// Page size of `list` when `--limit` is not given.
+    pub limit: usize,
+    /// Which sessions to show when neither `--all` nor `--cwd` is given.
+    pub scope: Scope,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            limit: 20,
+            scope: Scope::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResumeConfig {
+    /// Extra arguments placed between `resume` and the session id.
+    pub args: Vec<String>,
+    /// Number of sessions offered by the `resume` picker when `--limit` is not given.
+    pub picker_limit: usize,
+    /// Environment variables set for Codex.
+    pub env: BTreeMap<String, String>,
+    /// Settings for sessions recorded under a directory, applied after the ones above.
+    pub projects: BTreeMap<PathBuf, ResumeProfile>,
+}
+
+impl Default for ResumeConfig {
+    fn default() -> Self {
+        Self {
+            args: Vec::new(),
+            picker_limit: 25,
+            env: BTreeMap::new(),
+            projects: BTreeMap::new(),
+        }
+    }
+}
+
+/// Arguments and environment variables added when resuming a session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResumeProfile {
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Directory relative export paths are resolved against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
+
+impl ExportConfig {
+    /// Resolve an export destination, keeping absolute paths as they are.
+    pub fn resolve(&self, output: &Path) -> PathBuf {
+        match &self.dir {
+            Some(dir) if output.is_relative() => expand_home(dir).join(output),
+            _ => output.to_path_buf(),
+        }
+    }
+}
+
+/// Retention policy applied by `prune` when no criteria are passed on the command line.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PruneConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub older_than: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smaller_than: Option<String>,
+    pub empty: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_last: Option<usize>,
+}
+
+impl PruneConfig {
+    pub fn criteria(&self) -> Result<PruneCriteria> {
+        Ok(PruneCriteria {
+            older_than: self
+                .older_than
+                .as_deref()
+                .map(parse_age)
+                .transpose()
+                .map_err(|err| anyhow!("invalid prune.older_than: {err}"))?,
+            smaller_than: self
+                .smaller_than
+                .as_deref()
+                .map(parse_size)
+                .transpose()
+                .map_err(|err| anyhow!("invalid prune.smaller_than: {err}"))?,
+            empty: self.empty,
+            keep_last: self.keep_last,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashConfig {
+    /// Days a deleted session stays in the trash before it is purged; 0 keeps it forever.
+    pub retention_days: u32,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { retention_days: 30 }
+    }
+}
+
+/// Sort order and columns of the session table in `list` and the TUI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ViewConfig {
+    pub sort: SortKey,
+    pub columns: Vec<Column>,
+    /// Keep sessions of the same repository together in the TUI.
+    pub group_by_repo: bool,
+}
+
+impl Default for ViewConfig {
+    fn default() -> Self {
+        Self {
+            sort: SortKey::default(),
+            columns: Column::DEFAULT.to_vec(),
+            group_by_repo: false,
+        }
+    }
+}
+
+/// Look and key bindings of the TUI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    pub theme: Preset,
+    pub colors: ColorOverrides,
+    /// Keys by action name, replacing that action's built-in keys, e.g. `delete = "x"`.
+    pub keys: BTreeMap<Action, KeyList>,
+}
+
+/// Locate the config file.
+///
+/// An explicit path (`--config` or `CODEX_SESSION_CONFIG`) wins. Otherwise
+/// `$CODEX_HOME/codex-session.toml` is used, falling back to
+/// `$XDG_CONFIG_HOME/codex-session/config.toml` when only that one exists.
+pub fn config_path(codex_home: &Path, explicit: Option<&Path>) -> PathBuf {
+    if let Some(path) = explicit {
+        return path.to_path_buf();
+    }
+    let home_config = codex_home.join(CONFIG_FILE);
+    if !home_config.exists()
+        && let Some(xdg_config) =
+            dirs::config_dir().map(|dir| dir.join("codex-session").join("config.toml"))
+        && xdg_config.exists()
+    {
+        return xdg_config;
+    }
+    home_config
+}
+
+/// Load the config file, falling back to defaults when it does not exist.
+pub fn load(codex_home: &Path, explicit: Option<&Path>) -> Result<Config> {
+    let path = config_path(codex_home, explicit);
+    if !path.exists() {
+        if explicit.is_some() {
+            return Err(anyhow!("config file {path:?} does not exist"));
+        }
+        return Ok(Config {
+            path,
+            ..Config::default()
+        });
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("failed to read config {path:?}"))?;
+    let mut config: Config =
+        toml::from_str(&raw).with_context(|| format!("invalid config file {path:?}"))?;
+    config.path = path;
+    Ok(config)
+}
+
+/// Render the effective settings, defaults included, as TOML.
+pub fn render(config: &Config) -> Result<String> {
+    toml::to_string_pretty(config).context("failed to render config")
+}
+
+/// Create the config file from a commented template unless it already exists.
+pub fn ensure_exists(path: &Path) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory {parent:?}"))?;
+    }
+    fs::write(path, TEMPLATE).with_context(|| format!("failed to write config {path:?}"))
+}
+
+/// Write the `[view]` table back to the config file, leaving every other setting, comment
+/// and the order of keys as they were.
+pub fn save_view(path: &Path, view: &ViewConfig) -> Result<()> {
+    let mut doc: DocumentMut = if path.exists() {
+        let raw =
+            fs::read_to_string(path).with_context(|| format!("failed to read config {path:?}"))?;
+        raw.parse()
+            .with_context(|| format!("invalid config file {path:?}"))?
+    } else {
+        DocumentMut::new()
+    };
+    if !doc.contains_table("view") {
+        doc["view"] = toml_edit::table();
+    }
+    let table = doc["view"]
+        .as_table_mut()
+        .with_context(|| format!("`view` in {path:?} is not a table"))?;
+    table["sort"] = value(view.sort.as_str());
+    table["columns"] = value(Array::from_iter(view.columns.iter().map(|c| c.as_str())));
+    table["group_by_repo"] = value(view.group_by_repo);
+    fs::write(path, doc.to_string()).with_context(|| format!("failed to write config {path:?}"))
+}
+
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir, unique to this test run.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-session-config-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn view() -> ViewConfig {
+        ViewConfig {
+            sort: SortKey::Size,
+            columns: vec![Column::Id, Column::Size, Column::Conversation],
+            group_by_repo: true,
+        }
+    }
+
+    #[test]
+    fn the_template_loads_as_the_defaults() {
+        let home = scratch("template");
+        let path = home.join(CONFIG_FILE);
+        ensure_exists(&path).unwrap();
+        let config = load(&home, None).unwrap();
+        assert_eq!(config.path, path);
+        assert_eq!(config.view, ViewConfig::default());
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn unknown_keys_and_missing_explicit_files_are_errors() {
+        let home = scratch("errors");
+        let path = home.join(CONFIG_FILE);
+        fs::write(&path, "[list]\nlimt = 10\n").unwrap();
+        assert!(load(&home, None).is_err());
+        assert!(load(&home, Some(&home.join("missing.toml"))).is_err());
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn save_view_keeps_comments_and_other_settings() {
+        let home = scratch("save-view");
+        let path = home.join(CONFIG_FILE);
+        let original = "# my settings\n\
+                        codex_bin = \"/opt/codex\" # pinned build\n\
+                        \n\
+                        [view]\n\
+                        # the table I look at most\n\
+                        sort = \"created\"\n\
+                        \n\
+                        [list]\n\
+                        limit = 10\n";
+        fs::write(&path, original).unwrap();
+
+        save_view(&path, &view()).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# my settings\ncodex_bin = \"/opt/codex\" # pinned build\n"));
+        assert!(saved.contains("# the table I look at most\n"));
+        assert!(saved.find("[view]") < saved.find("[list]"));
+
+        let config = load(&home, Some(&path)).unwrap();
+        assert_eq!(config.view, view());
+        assert_eq!(config.codex_bin.as_deref(), Some("/opt/codex"));
+        assert_eq!(config.list.limit, 10);
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn save_view_creates_a_missing_file() {
+        let home = scratch("create-view");
+        let path = home.join(CONFIG_FILE);
+        save_view(&path, &view()).unwrap();
+        assert_eq!(load(&home, Some(&path)).unwrap().view, view());
+        fs::remove_dir_all(&home).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::session_store::SessionSummary;
+
+/// How a session's recorded branch and commit relate to the local checkout today.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitStatus {
+    /// The recorded branch no longer exists in the repository.
+    pub branch_missing: bool,
+    /// The recorded commit is not an ancestor of the current HEAD (rebased, reset or never fetched).
+    pub commit_diverged: bool,
+}
+
+impl GitStatus {
+    pub fn is_stale(&self) -> bool {
+        self.branch_missing || self.commit_diverged
+    }
+
+    pub fn label(&self) -> &'static str {
+        match (self.branch_missing, self.commit_diverged) {
+            (false, false) => "ok",
+            (true, false) => "branch gone",
+            (false, true) => "diverged",
+            (true, true) => "gone+diverged",
+        }
+    }
+}
+
+/// Asks `git` about session checkouts, caching the answers per repository.
+#[derive(Debug, Default)]
+pub struct GitChecker {
+    /// Top-level directory of each session CWD; `None` once it is no longer a checkout.
+    roots: HashMap<PathBuf, Option<PathBuf>>,
+    branches: HashMap<PathBuf, HashSet<String>>,
+    ancestors: HashMap<(PathBuf, String), bool>,
+}
+
+impl GitChecker {
+    /// Compare the recorded branch and commit with the checkout in the session's CWD.
+    ///
+    /// Returns `None` when nothing was recorded or the CWD is gone or no longer a repository.
+    pub fn status(&mut self, summary: &SessionSummary) -> Option<GitStatus> {
+        if summary.git_branch.is_none() && summary.git_commit.is_none() {
+            return None;
+        }
+        let root = self.root(summary.cwd.as_deref()?)?;
+        let branch_missing = summary
+            .git_branch
+            .as_deref()
+            .is_some_and(|branch| !self.branches(&root).contains(branch));
+        let commit_diverged = summary
+            .git_commit
+            .as_deref()
+            .is_some_and(|commit| !self.is_ancestor(&root, commit));
+        Some(GitStatus {
+            branch_missing,
+            commit_diverged,
+        })
+    }
+
+    /// Fill in `git_status` on every summary.
+    pub fn annotate(&mut self, sessions: &mut [SessionSummary]) {
+        for summary in sessions {
+            summary.git_status = self.status(summary);
+        }
+    }
+
+    fn root(&mut self, cwd: &Path) -> Option<PathBuf> {
+        self.roots
+            .entry(cwd.to_path_buf())
+            .or_insert_with(|| {
+                git(cwd, &["rev-parse", "--show-toplevel"]).map(|out| PathBuf::from(out.trim()))
+            })
+            .clone()
+    }
+
+    fn branches(&mut self, root: &Path) -> &HashSet<String> {
+        self.branches.entry(root.to_path_buf()).or_insert_with(|| {
+            git(
+                root,
+                &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+            )
+            .map(|out| out.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+        })
+    }
+
+    fn is_ancestor(&mut self, root: &Path, commit: &str) -> bool {
+        *self
+            .ancestors
+            .entry((root.to_path_buf(), commit.to_string()))
+            .or_insert_with(|| {
+                // A commit missing from the local object store fails the same way as one
+                // that is not in HEAD's history, and both count as diverged.
+                Command::new("git")
+                    .arg("-C")
+                    .arg(root)
+                    .args(["merge-base", "--is-ancestor", commit, "HEAD"])
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .is_ok_and(|status| status.success())
+            })
+    }
+}
+
+/// Top-level directories of every worktree of the repository containing `dir`, the
+/// worktree `dir` is in first. `None` outside a repository.
+pub fn worktree_roots(dir: &Path) -> Option<Vec<PathBuf>> {
+    let current = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim());
+    let mut roots = vec![current];
+    if let Some(list) = git(dir, &["worktree", "list", "--porcelain"]) {
+        for line in list.lines() {
+            if let Some(path) = line.strip_prefix("worktree ") {
+                let path = PathBuf::from(path);
+                if !roots.contains(&path) {
+                    roots.push(path);
+                }
+            }
+        }
+    }
+    Some(roots)
+}
+
+/// `dir` plus the same relative directory in every other worktree of its repository.
+pub fn worktree_equivalents(dir: &Path) -> Vec<PathBuf> {
+    let Some(roots) = worktree_roots(dir) else {
+        return vec![dir.to_path_buf()];
+    };
+    let resolved = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let Some(relative) = roots
+        .first()
+        .and_then(|root| resolved.strip_prefix(root).ok())
+    else {
+        return vec![dir.to_path_buf()];
+    };
+    roots.iter().map(|root| root.join(relative)).collect()
+}
+
+/// Short `owner/name` form of a remote URL, e.g. `openai/codex` for `git@github.com:openai/codex.git`.
+pub fn repo_label(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/').trim_end_matches(".git");
+    let parts: Vec<&str> = trimmed.rsplit(['/', ':']).take(2).collect();
+    match parts.as_slice() {
+        [name, owner] if !owner.is_empty() && !name.is_empty() => format!("{owner}/{name}"),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_label_shortens_remote_urls() {
+        assert_eq!(
+            repo_label("git@github.com:openai/codex.git"),
+            "openai/codex"
+        );
+        assert_eq!(
+            repo_label("https://github.com/openai/codex"),
+            "openai/codex"
+        );
+        assert_eq!(
+            repo_label("https://github.com/openai/codex.git/"),
+            "openai/codex"
+        );
+        assert_eq!(
+            repo_label("ssh://git@example.com:2222/team/app.git"),
+            "team/app"
+        );
+    }
+
+    #[test]
+    fn repo_label_keeps_urls_without_an_owner() {
+        assert_eq!(repo_label("codex"), "codex");
+        assert_eq!(repo_label("codex.git"), "codex");
+    }
+
+    #[test]
+    fn git_status_labels() {
+        assert_eq!(GitStatus::default().label(), "ok");
+        assert!(!GitStatus::default().is_stale());
+        let gone = GitStatus {
+            branch_missing: true,
+            commit_diverged: false,
+        };
+        assert_eq!(gone.label(), "branch gone");
+        assert!(gone.is_stale());
+    }
+}
@@ -0,0 +1,546 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Something the TUI can do in response to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Up,
+    Down,
+    Open,
+    Edit,
+    Filter,
+    Find,
+    Transcript,
+    Split,
+    Sort,
+    Columns,
+    Group,
+    Scope,
+    Mark,
+    Range,
+    MarkAll,
+    Pin,
+    Archive,
+    Delete,
+    Undo,
+    Command,
+    Help,
+    Quit,
+    Back,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Toggle,
+    Resume,
+    Jump,
+    ResumeOriginal,
+}
+
+impl Action {
+    pub const ALL: [Action; 31] = [
+        Action::Up,
+        Action::Down,
+        Action::Open,
+        Action::Edit,
+        Action::Filter,
+        Action::Find,
+        Action::Transcript,
+        Action::Split,
+        Action::Sort,
+        Action::Columns,
+        Action::Group,
+        Action::Scope,
+        Action::Mark,
+        Action::Range,
+        Action::MarkAll,
+        Action::Pin,
+        Action::Archive,
+        Action::Delete,
+        Action::Undo,
+        Action::Command,
+        Action::Help,
+        Action::Quit,
+        Action::Back,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Toggle,
+        Action::Resume,
+        Action::Jump,
+        Action::ResumeOriginal,
+    ];
+
+    /// Actions of the session list.
+    pub const LIST: [Action; 22] = [
+        Action::Up,
+        Action::Down,
+        Action::Open,
+        Action::Edit,
+        Action::Filter,
+        Action::Find,
+        Action::Transcript,
+        Action::Split,
+        Action::Sort,
+        Action::Columns,
+        Action::Group,
+        Action::Scope,
+        Action::Mark,
+        Action::Range,
+        Action::MarkAll,
+        Action::Pin,
+        Action::Archive,
+        Action::Delete,
+        Action::Undo,
+        Action::Command,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    /// Actions of the full-screen transcript; the transcript key closes it too.
+    pub const TRANSCRIPT: [Action; 8] = [
+        Action::Down,
+        Action::Up,
+        Action::PageDown,
+        Action::PageUp,
+        Action::Top,
+        Action::Bottom,
+        Action::Back,
+        Action::Transcript,
+    ];
+
+    /// Actions of the column picker; the columns key closes it too.
+    pub const COLUMNS: [Action; 5] = [
+        Action::Up,
+        Action::Down,
+        Action::Toggle,
+        Action::Back,
+        Action::Columns,
+    ];
+
+    /// Actions of the full-text search results.
+    pub const RESULTS: [Action; 4] = [Action::Up, Action::Down, Action::Open, Action::Back];
+
+    /// Actions of the dialog opened on a session.
+    pub const PROMPT: [Action; 5] = [
+        Action::Resume,
+        Action::Jump,
+        Action::Edit,
+        Action::ResumeOriginal,
+        Action::Back,
+    ];
+
+    /// Name used in the config file and key hints.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Open => "open",
+            Action::Edit => "edit",
+            Action::Filter => "filter",
+            Action::Find => "find",
+            Action::Transcript => "transcript",
+            Action::Split => "split",
+            Action::Sort => "sort",
+            Action::Columns => "columns",
+            Action::Group => "group",
+            Action::Scope => "scope",
+            Action::Mark => "mark",
+            Action::Range => "range",
+            Action::MarkAll => "mark_all",
+            Action::Pin => "pin",
+            Action::Archive => "archive",
+            Action::Delete => "delete",
+            Action::Undo => "undo",
+            Action::Command => "command",
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Toggle => "toggle",
+            Action::Resume => "resume",
+            Action::Jump => "jump",
+            Action::ResumeOriginal => "resume_original",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Up => "Move selection up",
+            Action::Down => "Move selection down",
+            Action::Open => "Resume or open a shell in the session's CWD",
+            Action::Edit => "Edit the resume command line, then run it",
+            Action::Filter => "Filter the list as you type",
+            Action::Find => "Find text in full transcripts",
+            Action::Transcript => "Open the transcript of the highlighted session",
+            Action::Split => "Toggle the list/transcript split layout",
+            Action::Sort => "Cycle the sort order",
+            Action::Columns => "Choose the visible columns",
+            Action::Group => "Group the list by repository",
+            Action::Scope => "Switch between this repository and all projects",
+            Action::Mark => "Toggle the highlighted session in the selection",
+            Action::Range => "Start or finish a range selection",
+            Action::MarkAll => "Select every filtered row",
+            Action::Pin => "Pin or unpin the selection",
+            Action::Archive => "Archive the selection",
+            Action::Delete => "Move the selection to the trash (press twice)",
+            Action::Undo => "Undo the last deletion",
+            Action::Command => "Enter command mode",
+            Action::Help => "Show this help",
+            Action::Quit => "Quit",
+            Action::Back => "Close the transcript, picker or dialog",
+            Action::PageUp => "Scroll up a page",
+            Action::PageDown => "Scroll down a page",
+            Action::Top => "Jump to the start",
+            Action::Bottom => "Jump to the end",
+            Action::Toggle => "Show or hide the highlighted column",
+            Action::Resume => "Resume in the current directory",
+            Action::Jump => "Open a shell in the session's CWD, then resume",
+            Action::ResumeOriginal => "Resume with the original model and sandbox",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Up => &["k", "up"],
+            Action::Down => &["j", "down"],
+            Action::Open => &["enter"],
+            Action::Edit => &["e"],
+            Action::Filter => &["/"],
+            Action::Find => &["f"],
+            Action::Transcript => &["l", "tab"],
+            Action::Split => &["s"],
+            Action::Sort => &["o"],
+            Action::Columns => &["c"],
+            Action::Group => &["g"],
+            Action::Scope => &["r"],
+            Action::Mark => &["space"],
+            Action::Range => &["V"],
+            Action::MarkAll => &["*"],
+            Action::Pin => &["p"],
+            Action::Archive => &["a"],
+            Action::Delete => &["d"],
+            Action::Undo => &["u"],
+            Action::Command => &[":"],
+            Action::Help => &["?"],
+            Action::Quit => &["q", "esc"],
+            Action::Back => &["esc", "q", "h", "n"],
+            Action::PageUp => &["b", "pgup"],
+            Action::PageDown => &["space", "pgdn"],
+            Action::Top => &["g", "home"],
+            Action::Bottom => &["G", "end"],
+            Action::Toggle => &["space", "enter"],
+            Action::Resume => &["r"],
+            Action::Jump => &["j"],
+            Action::ResumeOriginal => &["m"],
+        }
+    }
+}
+
+/// A key plus the modifiers that must be held, written like `x`, `ctrl-d` or `pgdn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // Shift is implied by the character itself (`V`, `?`, `*`), so it never has to match.
+        let held = key.modifiers.difference(KeyModifiers::SHIFT);
+        self.code == key.code && self.modifiers == held
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = value;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(stripped) = ["ctrl-", "c-"]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+                .filter(|stripped| !stripped.is_empty())
+            {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else if let Some(stripped) = ["alt-", "a-"]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+                .filter(|stripped| !stripped.is_empty())
+            {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{value}`")),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pgup"),
+            KeyCode::PageDown => f.write_str("pgdn"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// The keys of one action in the config file: a single key or a list of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyList(pub Vec<KeyBinding>);
+
+impl Serialize for KeyList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(ToString::to_string))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyListVisitor;
+
+        impl<'de> Visitor<'de> for KeyListVisitor {
+            type Value = KeyList;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a key such as \"x\" or \"ctrl-d\", or a list of keys")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<KeyList, E> {
+                value
+                    .parse()
+                    .map(|key| KeyList(vec![key]))
+                    .map_err(E::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KeyList, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(raw) = seq.next_element::<String>()? {
+                    keys.push(raw.parse().map_err(de::Error::custom)?);
+                }
+                Ok(KeyList(keys))
+            }
+        }
+
+        deserializer.deserialize_any(KeyListVisitor)
+    }
+}
+
+/// Which keys trigger which actions in the TUI.
+pub struct Keymap {
+    /// Configured actions come first so their keys shadow the built-in ones.
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Keymap {
+    /// Built-in bindings with the configured ones replacing them action by action.
+    pub fn new(overrides: &BTreeMap<Action, KeyList>) -> Self {
+        let mut bindings: Vec<(Action, Vec<KeyBinding>)> = Action::ALL
+            .iter()
+            .map(|action| {
+                let keys = match overrides.get(action) {
+                    Some(KeyList(keys)) => keys.clone(),
+                    None => action
+                        .default_keys()
+                        .iter()
+                        .map(|key| key.parse().expect("built-in key bindings are valid"))
+                        .collect(),
+                };
+                (*action, keys)
+            })
+            .collect();
+        bindings.sort_by_key(|(action, _)| !overrides.contains_key(action));
+        Self { bindings }
+    }
+
+    /// The action bound to a key press among `actions`, the ones the current view offers.
+    pub fn action(&self, key: &KeyEvent, actions: &[Action]) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|(action, _)| actions.contains(action))
+            .find(|(_, keys)| keys.iter().any(|binding| binding.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Keys bound to an action joined for display, e.g. `k/up`.
+    pub fn label(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(candidate, _)| *candidate == action)
+            .map(|(_, keys)| {
+                keys.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| String::from("(unbound)"))
+    }
+
+    /// Compact `keys=action` list for a status line.
+    pub fn hint(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .map(|action| format!("{}={}", self.label(*action), action.name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// One `keys  description` line per action, for the help overlay.
+    pub fn help_lines(&self, actions: &[Action]) -> Vec<(String, &'static str)> {
+        actions
+            .iter()
+            .map(|action| (self.label(*action), action.description()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn binding(raw: &str) -> KeyBinding {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn key_names_round_trip() {
+        for raw in [
+            "x",
+            "V",
+            "ctrl-d",
+            "alt-enter",
+            "space",
+            "pgdn",
+            "f5",
+            "esc",
+        ] {
+            assert_eq!(binding(raw).to_string(), raw);
+        }
+        assert_eq!(binding("C-d"), binding("ctrl-d"));
+        assert_eq!(binding("PageDown"), binding("pgdn"));
+        assert!("f13".parse::<KeyBinding>().is_err());
+        assert!("bogus".parse::<KeyBinding>().is_err());
+    }
+
+    #[test]
+    fn shift_is_implied_by_the_character() {
+        let shifted = KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT);
+        assert!(binding("V").matches(&shifted));
+        let ctrl = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert!(binding("ctrl-d").matches(&ctrl));
+        assert!(!binding("d").matches(&ctrl));
+    }
+
+    #[test]
+    fn keys_resolve_within_the_current_view() {
+        let keymap = Keymap::new(&BTreeMap::new());
+        let j = press(KeyCode::Char('j'));
+        assert_eq!(keymap.action(&j, &Action::LIST), Some(Action::Down));
+        assert_eq!(keymap.action(&j, &Action::PROMPT), Some(Action::Jump));
+        let space = press(KeyCode::Char(' '));
+        assert_eq!(keymap.action(&space, &Action::LIST), Some(Action::Mark));
+        assert_eq!(
+            keymap.action(&space, &Action::TRANSCRIPT),
+            Some(Action::PageDown)
+        );
+        assert_eq!(
+            keymap.action(&space, &Action::COLUMNS),
+            Some(Action::Toggle)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('z')), &Action::LIST),
+            None
+        );
+    }
+
+    #[test]
+    fn configured_keys_replace_and_shadow_the_built_in_ones() {
+        let overrides: BTreeMap<Action, KeyList> =
+            toml::from_str("delete = \"x\"\nup = [\"j\", \"ctrl-p\"]\n").unwrap();
+        let keymap = Keymap::new(&overrides);
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('x')), &Action::LIST),
+            Some(Action::Delete)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('d')), &Action::LIST),
+            None
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('j')), &Action::LIST),
+            Some(Action::Up)
+        );
+        assert_eq!(keymap.label(Action::Up), "j/ctrl-p");
+        assert_eq!(keymap.hint(&[Action::Delete]), "x=delete");
+    }
+}
@@ -0,0 +1,355 @@
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command as ProcessCommand;
+
+use crate::config::{ResumeConfig, ResumeProfile, expand_home};
+use crate::session_store::{SessionSummary, TurnSettings};
+
+/// How `codex resume` is spawned: the binary, the arguments placed before the session id
+/// and the environment it runs with.
+#[derive(Debug, Clone)]
+pub struct ResumeLauncher {
+    codex_bin: String,
+    config: ResumeProfile,
+    /// Per-project settings by expanded directory, most specific first.
+    projects: Vec<(PathBuf, ResumeProfile)>,
+    /// Given on the command line; applied after the config file.
+    overrides: ResumeProfile,
+}
+
+impl ResumeLauncher {
+    pub fn new(codex_bin: String, config: &ResumeConfig) -> Self {
+        let mut projects: Vec<_> = config
+            .projects
+            .iter()
+            .map(|(dir, profile)| (expand_home(dir), profile.clone()))
+            .collect();
+        projects.sort_by_key(|(dir, _)| Reverse(dir.components().count()));
+        Self {
+            codex_bin,
+            config: ResumeProfile {
+                args: config.args.clone(),
+                env: config.env.clone(),
+            },
+            projects,
+            overrides: ResumeProfile::default(),
+        }
+    }
+
+    /// Add arguments and variables from the command line.
+    pub fn with_overrides(&self, args: &[String], env: &[(String, String)]) -> Self {
+        let mut launcher = self.clone();
+        launcher.overrides.args.extend(args.iter().cloned());
+        launcher.overrides.env.extend(env.iter().cloned());
+        launcher
+    }
+
+    /// The command that resumes `summary`: the global settings, then those of the
+    /// innermost project containing its CWD, then the command line.
+    pub fn command(&self, summary: &SessionSummary) -> ResumeCommand {
+        let project = summary.cwd.as_deref().and_then(|cwd| {
+            self.projects
+                .iter()
+                .find(|(dir, _)| cwd.starts_with(dir))
+                .map(|(_, profile)| profile)
+        });
+        let mut argv = vec![self.codex_bin.clone(), "resume".to_string()];
+        let mut env = BTreeMap::new();
+        for profile in [Some(&self.config), project, Some(&self.overrides)]
+            .into_iter()
+            .flatten()
+        {
+            argv.extend(profile.args.iter().cloned());
+            env.extend(profile.env.clone());
+        }
+        argv.push(summary.id.clone());
+        ResumeCommand { env, argv }
+    }
+
+    /// Like `command`, plus the flags that restore the model and permissions of `original`.
+    /// Options the configured or command-line arguments already set are left to them.
+    pub fn command_matching(
+        &self,
+        summary: &SessionSummary,
+        original: &TurnSettings,
+    ) -> ResumeCommand {
+        let mut command = self.command(summary);
+        let id = command.argv.pop();
+        let flags = original_flags(original, &command.argv);
+        command.argv.extend(flags);
+        command.argv.extend(id);
+        command
+    }
+}
+
+/// Codex options that switch approval and sandbox behaviour as a whole.
+const PERMISSION_PRESETS: [&str; 2] = ["--full-auto", "--dangerously-bypass-approvals-and-sandbox"];
+
+fn original_flags(original: &TurnSettings, argv: &[String]) -> Vec<String> {
+    let mut flags = Vec::new();
+    if !is_set(argv, &["--model", "-m"]) {
+        flags.extend(["--model".to_string(), original.model.clone()]);
+    }
+    if let Some(effort) = original.effort.as_deref()
+        && !is_set(argv, &["model_reasoning_effort"])
+    {
+        flags.extend(["-c".to_string(), format!("model_reasoning_effort={effort}")]);
+    }
+    if let Some(policy) = original.approval_policy.as_deref()
+        && !is_set(argv, &["--ask-for-approval", "-a"])
+        && !is_set(argv, &PERMISSION_PRESETS)
+    {
+        flags.extend(["--ask-for-approval".to_string(), policy.to_string()]);
+    }
+    if let Some(mode) = original.sandbox.as_deref()
+        && !is_set(argv, &["--sandbox", "-s"])
+        && !is_set(argv, &PERMISSION_PRESETS)
+    {
+        flags.extend(["--sandbox".to_string(), mode.to_string()]);
+    }
+    if original.network_access && !is_set(argv, &["sandbox_workspace_write.network_access"]) {
+        flags.extend([
+            "-c".to_string(),
+            "sandbox_workspace_write.network_access=true".to_string(),
+        ]);
+    }
+    if !original.writable_roots.is_empty()
+        && !is_set(argv, &["sandbox_workspace_write.writable_roots"])
+    {
+        // A JSON array of strings is also a valid TOML one, which is what `-c` expects.
+        let roots = serde_json::to_string(&original.writable_roots).unwrap_or_default();
+        flags.extend([
+            "-c".to_string(),
+            format!("sandbox_workspace_write.writable_roots={roots}"),
+        ]);
+    }
+    flags
+}
+
+/// Whether `argv` already has one of these options, as a word or as `name=value`.
+fn is_set(argv: &[String], names: &[&str]) -> bool {
+    argv.iter().any(|arg| {
+        names.iter().any(|name| {
+            arg.strip_prefix(name)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+        })
+    })
+}
+
+/// A fully resolved command line: environment variables plus the program and its arguments.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeCommand {
+    pub env: BTreeMap<String, String>,
+    pub argv: Vec<String>,
+}
+
+impl ResumeCommand {
+    /// Shell-quoted form, with variables written as leading `NAME=value` words.
+    pub fn to_shell(&self) -> String {
+        self.env
+            .iter()
+            .map(|(key, value)| format!("{key}={}", shell_words::quote(value)))
+            .chain(
+                self.argv
+                    .iter()
+                    .map(|arg| shell_words::quote(arg).into_owned()),
+            )
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Parse a line written like `to_shell` produces.
+    pub fn parse(line: &str) -> Result<Self> {
+        let words = shell_words::split(line).context("unbalanced quotes in command line")?;
+        let mut env = BTreeMap::new();
+        let mut words = words.into_iter().peekable();
+        while let Some((key, value)) = words.peek().and_then(|word| parse_assignment(word)) {
+            env.insert(key, value);
+            words.next();
+        }
+        let argv: Vec<String> = words.collect();
+        if argv.is_empty() {
+            bail!("the command line has no program to run");
+        }
+        Ok(Self { env, argv })
+    }
+
+    /// Words for TSV output: the variables as `NAME=value`, then the arguments.
+    pub fn words(&self) -> Vec<String> {
+        self.env
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .chain(self.argv.iter().cloned())
+            .collect()
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let program = &self.argv[0];
+        let status = ProcessCommand::new(program)
+            .args(&self.argv[1..])
+            .envs(&self.env)
+            .status()
+            .with_context(|| format!("failed to spawn {program}"))?;
+        if !status.success() {
+            bail!("codex exited with status {status}");
+        }
+        Ok(())
+    }
+}
+
+/// Split `NAME=value` into its parts; `None` unless `NAME` is a valid variable name.
+pub fn parse_assignment(word: &str) -> Option<(String, String)> {
+    let (key, value) = word.split_once('=')?;
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| (key.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_splits_leading_assignments_from_the_command() {
+        let command = ResumeCommand::parse("RUST_LOG=debug codex resume 'my id'").unwrap();
+        assert_eq!(
+            command.env,
+            BTreeMap::from([("RUST_LOG".to_string(), "debug".to_string())])
+        );
+        assert_eq!(command.argv, strings(&["codex", "resume", "my id"]));
+    }
+
+    #[test]
+    fn parse_keeps_assignments_after_the_program_as_arguments() {
+        let command = ResumeCommand::parse("codex resume -c model=o3 1=x").unwrap();
+        assert!(command.env.is_empty());
+        assert_eq!(
+            command.argv,
+            strings(&["codex", "resume", "-c", "model=o3", "1=x"])
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unbalanced_quotes_and_empty_commands() {
+        assert!(ResumeCommand::parse("codex resume 'abc").is_err());
+        assert!(ResumeCommand::parse("").is_err());
+        assert!(ResumeCommand::parse("FOO=bar").is_err());
+    }
+
+    #[test]
+    fn to_shell_round_trips_through_parse() {
+        let command = ResumeCommand {
+            env: BTreeMap::from([("GREETING".to_string(), "hello world".to_string())]),
+            argv: strings(&["codex", "resume", "--search", "it's", "abc-123"]),
+        };
+        let line = command.to_shell();
+        assert!(line.starts_with("GREETING='hello world' codex resume --search "));
+        let parsed = ResumeCommand::parse(&line).unwrap();
+        assert_eq!(parsed.env, command.env);
+        assert_eq!(parsed.argv, command.argv);
+    }
+
+    fn settings() -> TurnSettings {
+        TurnSettings {
+            model: "gpt-5-codex".to_string(),
+            effort: Some("high".to_string()),
+            approval_policy: Some("on-request".to_string()),
+            sandbox: Some("workspace-write".to_string()),
+            network_access: true,
+            writable_roots: vec![PathBuf::from("/tmp/cache")],
+        }
+    }
+
+    #[test]
+    fn is_set_matches_whole_options_and_assignments() {
+        let argv = strings(&[
+            "codex",
+            "resume",
+            "--model=o3",
+            "-c",
+            "model_reasoning_effort=low",
+        ]);
+        assert!(is_set(&argv, &["--model", "-m"]));
+        assert!(is_set(&argv, &["model_reasoning_effort"]));
+        assert!(!is_set(&argv, &["--sandbox", "-s"]));
+        assert!(!is_set(
+            &strings(&["--model-provider", "oss"]),
+            &["--model"]
+        ));
+    }
+
+    #[test]
+    fn original_flags_restore_every_recorded_setting() {
+        let flags = original_flags(&settings(), &strings(&["codex", "resume"]));
+        assert_eq!(
+            flags,
+            strings(&[
+                "--model",
+                "gpt-5-codex",
+                "-c",
+                "model_reasoning_effort=high",
+                "--ask-for-approval",
+                "on-request",
+                "--sandbox",
+                "workspace-write",
+                "-c",
+                "sandbox_workspace_write.network_access=true",
+                "-c",
+                "sandbox_workspace_write.writable_roots=[\"/tmp/cache\"]",
+            ])
+        );
+    }
+
+    #[test]
+    fn original_flags_leave_configured_options_alone() {
+        let argv = strings(&["codex", "resume", "-m", "o3", "--full-auto"]);
+        let flags = original_flags(&settings(), &argv);
+        assert!(!flags.contains(&"--model".to_string()));
+        assert!(!flags.contains(&"--ask-for-approval".to_string()));
+        assert!(!flags.contains(&"--sandbox".to_string()));
+        assert!(flags.contains(&"model_reasoning_effort=high".to_string()));
+    }
+
+    #[test]
+    fn original_flags_skip_settings_that_were_not_recorded() {
+        let original = TurnSettings {
+            effort: None,
+            approval_policy: None,
+            sandbox: None,
+            network_access: false,
+            writable_roots: Vec::new(),
+            ..settings()
+        };
+        let flags = original_flags(&original, &strings(&["codex", "resume"]));
+        assert_eq!(flags, strings(&["--model", "gpt-5-codex"]));
+    }
+
+    #[test]
+    fn parse_assignment_needs_a_variable_name() {
+        assert_eq!(
+            parse_assignment("OPENAI_API_BASE=http://localhost"),
+            Some((
+                "OPENAI_API_BASE".to_string(),
+                "http://localhost".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_assignment("_X="),
+            Some(("_X".to_string(), String::new()))
+        );
+        assert_eq!(parse_assignment("=value"), None);
+        assert_eq!(parse_assignment("1X=value"), None);
+        assert_eq!(parse_assignment("--flag=value"), None);
+        assert_eq!(parse_assignment("no-assignment"), None);
+    }
+}
@@ -1,21 +1,53 @@
+mod annotations;
+mod archive;
+mod auto_title;
 mod cli;
 mod codex_home;
+mod columns;
+mod config;
+mod git;
+mod keymap;
+mod launcher;
+mod output;
+mod pricing;
+mod prune;
+mod search;
+mod session_index;
 mod session_store;
+mod stats;
+mod theme;
+mod transcript_view;
+mod trash;
 mod tui;
 
+use annotations::AnnotationStore;
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
 use chrono_humanize::HumanTime;
 use clap::Parser;
-use cli::{Cli, Command, DeleteArgs, InfoArgs, ListArgs, ResumeArgs};
+use cli::{
+    ArchiveArgs, Cli, Command, ConfigCommand, DeleteArgs, ExportArgs, IndexArgs, IndexCommand,
+    InfoArgs, ListArgs, NoteArgs, PruneArgs, RenameArgs, ResumeArgs, ScopeArgs, SearchArgs,
+    StatsArgs, TagArgs, TagCommand, TrashArgs, TrashCommand,
+};
+use columns::Column;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Cell, Table};
+use config::{Config, Scope};
 use dialoguer::{Confirm, FuzzySelect};
+use git::GitChecker;
+use launcher::ResumeLauncher;
+use output::Format;
 use owo_colors::OwoColorize;
+use pricing::PriceTable;
+use search::search_sessions;
+use serde::Serialize;
 use session_store::{
-    ListOptions, SessionDetail, SessionSummary, list_sessions, load_session_detail,
-    resolve_session_path,
+    CwdScope, EntryKind, ExportOptions, ListOptions, SessionDetail, SessionSummary, SourceKind,
+    export_session_chat, list_sessions, load_session_detail, read_turn_settings,
+    resolve_session_path, sort_sessions,
 };
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
@@ -24,34 +56,108 @@ use tui::{TuiOutcome, run as run_tui};
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let codex_home = codex_home::resolve(cli.codex_home)?;
+    // `config path` and `config edit` must keep working while the file does not parse.
+    if let Some(Command::Config(args)) = &cli.command
+        && !matches!(args.command, ConfigCommand::Show)
+    {
+        let path = config::config_path(&codex_home, cli.config.as_deref());
+        return run_config_file(&codex_home, &path, &args.command, cli.format);
+    }
+    let config = config::load(&codex_home, cli.config.as_deref())?;
+    // Precedence: --codex-bin, then CODEX_SESSION_CODEX_BIN (both via clap), then the config file.
+    let launcher = ResumeLauncher::new(
+        cli.codex_bin
+            .or_else(|| config.codex_bin.clone())
+            .unwrap_or_else(|| config::DEFAULT_CODEX_BIN.to_string()),
+        &config.resume,
+    );
+    let format = cli.format;
+    let prices = PriceTable::new(&config.prices);
+    if config.trash.retention_days > 0 {
+        // Purging is housekeeping; never let it block the command the user asked for.
+        let _ = trash::purge_older_than(
+            &codex_home,
+            chrono::Duration::days(config.trash.retention_days.into()),
+        );
+    }
 
     match cli.command {
-        Some(Command::List(args)) => run_list(&codex_home, args)?,
-        Some(Command::Resume(args)) => run_resume(&codex_home, args, &cli.codex_bin)?,
-        Some(Command::Info(args)) => run_info(&codex_home, args)?,
-        Some(Command::Delete(args)) => run_delete(&codex_home, args)?,
-        None => run_interactive(&codex_home, &cli.codex_bin)?,
+        Some(Command::List(args)) => run_list(&codex_home, args, &prices, &config, format)?,
+        Some(Command::Resume(args)) => run_resume(&codex_home, args, &launcher, &config, format)?,
+        Some(Command::Info(args)) => run_info(&codex_home, args, &prices, format)?,
+        Some(Command::Search(args)) => run_search(&codex_home, args, &config, format)?,
+        Some(Command::Stats(args)) => run_stats(&codex_home, args, &prices, &config, format)?,
+        Some(Command::Export(args)) => run_export(&codex_home, args, &config, format)?,
+        Some(Command::Archive(args)) => run_archive(&codex_home, args, format)?,
+        Some(Command::Unarchive(args)) => run_unarchive(&codex_home, args, format)?,
+        Some(Command::Delete(args)) => run_delete(&codex_home, args, format)?,
+        Some(Command::Trash(args)) => run_trash(&codex_home, args, format)?,
+        Some(Command::Prune(args)) => run_prune(&codex_home, args, &config, format)?,
+        Some(Command::Tag(args)) => run_tag(&codex_home, args, format)?,
+        Some(Command::Note(args)) => run_note(&codex_home, args, format)?,
+        Some(Command::Rename(args)) => run_rename(&codex_home, args, format)?,
+        Some(Command::Pin(args)) => run_pin(&codex_home, args, true, format)?,
+        Some(Command::Unpin(args)) => run_pin(&codex_home, args, false, format)?,
+        Some(Command::Index(args)) => run_index(&codex_home, args, format)?,
+        // Only `config show` gets here; the other subcommands ran before loading.
+        Some(Command::Config(_)) if format.is_table() => print!("{}", config::render(&config)?),
+        Some(Command::Config(_)) => output::emit(format, &config)?,
+        None => run_interactive(&codex_home, &launcher, &prices, &config)?,
     }
 
     Ok(())
 }
 
-fn run_interactive(codex_home: &Path, codex_bin: &str) -> Result<()> {
-    let opts = ListOptions {
+fn run_interactive(
+    codex_home: &Path,
+    launcher: &ResumeLauncher,
+    prices: &PriceTable,
+    config: &Config,
+) -> Result<()> {
+    let project = default_scope(match config.list.scope {
+        Scope::All => Scope::Repo,
+        scope => scope,
+    });
+    let opts = |scope: CwdScope| ListOptions {
         limit: 500,
         cursor: None,
         providers: Vec::new(),
-        show_all: true,
-        cwd_filter: None,
+        scope,
+        remap: config.path_remap(),
+        archived: false,
+        tags: Vec::new(),
+        sources: SourceKind::INTERACTIVE.to_vec(),
+        include_empty: false,
+        repo: None,
+        branch: None,
+        commit: None,
     };
-    let list = list_sessions(codex_home, &opts)?;
-    if let Some(outcome) = run_tui(list.sessions)? {
+    // Both the newest sessions overall and this project's own are loaded so the TUI can
+    // switch between them; busy projects elsewhere would otherwise crowd this one out.
+    let mut sessions = list_sessions(codex_home, &opts(CwdScope::All))?.into_all();
+    if project != CwdScope::All {
+        let loaded: HashSet<PathBuf> = sessions.iter().map(|s| s.path.clone()).collect();
+        sessions.extend(
+            list_sessions(codex_home, &opts(project.clone()))?
+                .into_all()
+                .into_iter()
+                .filter(|session| !loaded.contains(&session.path)),
+        );
+    }
+    if let Some(outcome) = run_tui(
+        codex_home,
+        sessions,
+        project,
+        prices.clone(),
+        launcher,
+        config,
+    )? {
         match outcome {
-            TuiOutcome::Resume(summary) => {
+            TuiOutcome::Resume(summary, command) => {
                 println!("Resuming session {}", summary.id.cyan());
-                resume_session(codex_bin, &summary.id)?;
+                command.run()?;
             }
-            TuiOutcome::Jump(summary) => {
+            TuiOutcome::Jump(summary, command) => {
                 if let Some(cwd) = summary.cwd.as_ref() {
                     std::env::set_current_dir(cwd)
                         .with_context(|| format!("failed to cd to {}", cwd.display()))?;
@@ -60,48 +166,144 @@ fn run_interactive(codex_home: &Path, codex_bin: &str) -> Result<()> {
                     println!("No CWD recorded; staying in current directory");
                 }
                 println!("Resuming session {}", summary.id.cyan());
-                resume_session(codex_bin, &summary.id)?;
+                command.run()?;
             }
         }
     }
     Ok(())
 }
 
-fn resolve_scope(all: bool, cwd: Option<PathBuf>) -> (bool, Option<PathBuf>) {
-    if let Some(dir) = cwd {
-        (false, Some(dir))
-    } else if all {
-        (true, None)
+fn resolve_scope(args: &ScopeArgs, default: Scope) -> Result<CwdScope> {
+    let Some(cwd) = args.cwd.as_deref() else {
+        return Ok(if args.all {
+            CwdScope::All
+        } else {
+            default_scope(default)
+        });
+    };
+    let cwd = config::expand_home(cwd);
+    let raw = cwd.to_string_lossy();
+    if raw.contains(['*', '?', '[']) {
+        if args.worktrees {
+            bail!("--worktrees needs a directory, not a --cwd pattern");
+        }
+        let pattern =
+            glob::Pattern::new(&raw).with_context(|| format!("invalid --cwd pattern `{raw}`"))?;
+        return Ok(CwdScope::Glob {
+            pattern,
+            recursive: args.cwd_recursive,
+        });
+    }
+    let dirs = if args.worktrees {
+        git::worktree_equivalents(&cwd)
+    } else {
+        vec![cwd]
+    };
+    Ok(if args.cwd_recursive {
+        CwdScope::Within(dirs)
     } else {
-        (true, None)
+        CwdScope::Exact(dirs)
+    })
+}
+
+/// The directories `scope` covers when run from the current directory.
+fn default_scope(scope: Scope) -> CwdScope {
+    let Ok(here) = std::env::current_dir() else {
+        return CwdScope::All;
+    };
+    match scope {
+        Scope::All => CwdScope::All,
+        Scope::Cwd => CwdScope::Exact(vec![here]),
+        // Outside a repository, the current directory is the closest thing to a project.
+        Scope::Repo => git::worktree_roots(&here)
+            .map(CwdScope::Within)
+            .unwrap_or_else(|| CwdScope::Exact(vec![here])),
     }
 }
 
-fn run_list(codex_home: &Path, args: ListArgs) -> Result<()> {
-    let (show_all, cwd_filter) = resolve_scope(args.all, args.cwd.clone());
+fn run_list(
+    codex_home: &Path,
+    args: ListArgs,
+    prices: &PriceTable,
+    config: &Config,
+    format: Format,
+) -> Result<()> {
+    let format = format.or_json(args.json);
+    let sort = args.sort.unwrap_or(config.view.sort);
+    let columns = if args.columns.is_empty() {
+        config.view.columns.clone()
+    } else {
+        args.columns.clone()
+    };
+    let scope = resolve_scope(&args.scope, config.list.scope)?;
+    let limit = args.limit.unwrap_or(config.list.limit).max(1);
+    // Pages follow the scan order, so other keys are sorted over the whole matching set.
+    let whole_set = !sort.is_chronological();
+    if whole_set && args.cursor.is_some() {
+        bail!("--cursor pages through sessions by time; it cannot be combined with --sort {sort}");
+    }
 
     let opts = ListOptions {
-        limit: args.limit.max(1),
+        limit: if whole_set { usize::MAX } else { limit },
         cursor: args.cursor.clone(),
         providers: args.providers.clone(),
-        show_all,
-        cwd_filter,
+        scope,
+        remap: config.path_remap(),
+        archived: args.archived,
+        tags: args.tags.clone(),
+        sources: args.sources.clone(),
+        include_empty: args.include_empty,
+        repo: args.repo.clone(),
+        branch: args.branch.clone(),
+        commit: args.commit.clone(),
     };
 
-    let list = list_sessions(codex_home, &opts)?;
+    let mut list = list_sessions(codex_home, &opts)?;
+    sort_sessions(&mut list.pinned, sort);
+    sort_sessions(&mut list.sessions, sort);
+    if whole_set {
+        list.sessions.truncate(limit);
+        list.next_cursor = None;
+    }
+    // Checking the checkout spawns `git`, so only do it when the result is shown.
+    if columns.contains(&Column::Git) || matches!(format, Format::Json | Format::Jsonl) {
+        let mut git = GitChecker::default();
+        git.annotate(&mut list.pinned);
+        git.annotate(&mut list.sessions);
+    }
 
-    if args.json {
-        let payload = serde_json::json!({
-            "sessions": list.sessions,
-            "next_cursor": list.next_cursor,
-            "scanned_files": list.scanned_files,
-            "reached_scan_cap": list.reached_scan_cap,
-        });
-        println!("{}", serde_json::to_string_pretty(&payload)?);
-        return Ok(());
+    match format {
+        Format::Table => {}
+        Format::Json => {
+            let payload = serde_json::json!({
+                "pinned": list.pinned,
+                "sessions": list.sessions,
+                "next_cursor": list.next_cursor,
+                "scanned_files": list.scanned_files,
+                "reached_scan_cap": list.reached_scan_cap,
+            });
+            return output::emit(format, &payload);
+        }
+        Format::Jsonl => return output::emit_records(format, &list.into_all()),
+        Format::Tsv => {
+            output::print_tsv_row(&columns.iter().map(|c| c.as_str()).collect::<Vec<_>>());
+            for summary in list.pinned.iter().chain(&list.sessions) {
+                output::print_tsv_row(
+                    &columns
+                        .iter()
+                        .map(|column| column.value(summary))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            return Ok(());
+        }
     }
 
-    if list.sessions.is_empty() {
+    if list.pinned.is_empty() && list.sessions.is_empty() && args.archived {
+        println!("{}", "No archived sessions were found.".yellow());
+        return Ok(());
+    }
+    if list.pinned.is_empty() && list.sessions.is_empty() {
         println!("{}", "No Codex sessions were found.".yellow());
         println!(
             "Use {} to focus on a directory (e.g. {}).",
@@ -111,33 +313,14 @@ fn run_list(codex_home: &Path, args: ListArgs) -> Result<()> {
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(UTF8_FULL);
-    table.set_header(vec!["Updated", "Branch", "CWD", "Conversation"]);
-
-    for summary in &list.sessions {
-        let updated = summary
-            .updated_at
-            .map(|dt| format_relative(dt))
-            .unwrap_or_else(|| "unknown".to_string());
-        let preview = summary
-            .preview
-            .as_deref()
-            .unwrap_or("(no user message yet)");
-        let cwd = summary
-            .cwd
-            .as_ref()
-            .map(|path| shorten_path(path, 28))
-            .unwrap_or_else(|| "(unknown)".into());
-        table.add_row(vec![
-            Cell::new(updated),
-            Cell::new(summary.git_branch.as_deref().unwrap_or("-")),
-            Cell::new(cwd),
-            Cell::new(truncate_preview(preview)),
-        ]);
+    if !list.pinned.is_empty() {
+        println!("{}", "Pinned".bold());
+        println!("{}", session_table(&list.pinned, &columns, prices));
+        println!();
+    }
+    if !list.sessions.is_empty() {
+        println!("{}", session_table(&list.sessions, &columns, prices));
     }
-
-    println!("{}", table);
     println!(
         "Scanned {} files{}.",
         list.scanned_files,
@@ -155,7 +338,15 @@ fn run_list(codex_home: &Path, args: ListArgs) -> Result<()> {
         );
     }
 
-    if let Some(first) = list.sessions.first() {
+    let first = list.pinned.first().or(list.sessions.first());
+    if args.archived {
+        if let Some(first) = first {
+            println!(
+                "To restore, run {}.",
+                format!("codex-session unarchive {}", first.id).cyan()
+            );
+        }
+    } else if let Some(first) = first {
         println!(
             "To resume, run {} ({}).",
             first.resume_hint(),
@@ -170,61 +361,133 @@ fn run_list(codex_home: &Path, args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_resume(codex_home: &Path, args: ResumeArgs, codex_bin: &str) -> Result<()> {
+fn session_table(sessions: &[SessionSummary], columns: &[Column], prices: &PriceTable) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(columns.iter().map(|column| column.header()));
+    for summary in sessions {
+        table.add_row(
+            columns
+                .iter()
+                .map(|column| Cell::new(column.cell(summary, prices))),
+        );
+    }
+    table
+}
+
+fn run_resume(
+    codex_home: &Path,
+    args: ResumeArgs,
+    launcher: &ResumeLauncher,
+    config: &Config,
+    format: Format,
+) -> Result<()> {
     let summary = if let Some(query) = args.session.as_deref() {
         let path = resolve_session_path(codex_home, query)?;
-        load_session_detail(codex_home, &path)?.summary
+        let mut summary = load_session_detail(codex_home, &path)?.summary;
+        // Listings are remapped already; do the same here so project settings still apply.
+        if let Some(cwd) = summary
+            .cwd
+            .as_deref()
+            .and_then(|cwd| config.path_remap().apply(cwd))
+        {
+            summary.cwd = Some(cwd);
+        }
+        summary
     } else if args.last {
-        let opts = build_resume_list_opts(&args)?;
+        let opts = build_resume_list_opts(&args, config)?;
         let list = list_sessions(codex_home, &opts)?;
-        list.sessions
+        list.into_all()
             .into_iter()
-            .next()
+            .max_by_key(|summary| summary.updated_at)
             .ok_or_else(|| anyhow::anyhow!("No recorded sessions found"))?
     } else {
-        prompt_for_session(codex_home, &args)?
+        prompt_for_session(codex_home, &args, config)?
     };
 
+    let launcher = launcher.with_overrides(&args.codex_args, &args.env);
+    let original = if args.match_original {
+        let original = read_turn_settings(&summary.path)?;
+        if original.is_none() {
+            eprintln!(
+                "{} session {} recorded no model or sandbox settings; using the defaults",
+                "Warning:".yellow(),
+                summary.id
+            );
+        }
+        original
+    } else {
+        None
+    };
+    let command = match original.as_ref() {
+        Some(original) => launcher.command_matching(&summary, original),
+        None => launcher.command(&summary),
+    };
     if args.dry_run {
-        println!(
-            "{}",
-            format!("{} {} {}", codex_bin, "resume", summary.id).cyan()
-        );
+        match format {
+            Format::Table => println!("{}", command.to_shell().cyan()),
+            Format::Tsv => output::print_tsv_row(&command.words()),
+            _ => output::emit(
+                format,
+                &serde_json::json!({
+                    "session": summary.id,
+                    "cwd": summary.cwd,
+                    "env": command.env,
+                    "argv": command.argv,
+                }),
+            )?,
+        }
         return Ok(());
     }
 
-    println!("Resuming session {}", summary.id.cyan());
-    resume_session(codex_bin, &summary.id)
+    if format.is_table() {
+        println!("Resuming session {}", summary.id.cyan());
+    }
+    command.run()
 }
 
-fn build_resume_list_opts(args: &ResumeArgs) -> Result<ListOptions> {
-    let (show_all, cwd_filter) = resolve_scope(args.all, args.cwd.clone());
+fn build_resume_list_opts(args: &ResumeArgs, config: &Config) -> Result<ListOptions> {
+    let scope = resolve_scope(&args.scope, config.list.scope)?;
 
     Ok(ListOptions {
-        limit: args.limit.max(1),
+        limit: args.limit.unwrap_or(config.resume.picker_limit).max(1),
         cursor: None,
         providers: Vec::new(),
-        show_all,
-        cwd_filter,
+        scope,
+        remap: config.path_remap(),
+        archived: false,
+        tags: Vec::new(),
+        sources: if args.sources.is_empty() {
+            SourceKind::INTERACTIVE.to_vec()
+        } else {
+            args.sources.clone()
+        },
+        include_empty: false,
+        repo: None,
+        branch: None,
+        commit: None,
     })
 }
 
-fn prompt_for_session(codex_home: &Path, args: &ResumeArgs) -> Result<SessionSummary> {
-    let opts = build_resume_list_opts(args)?;
-    let list = list_sessions(codex_home, &opts)?;
-    if list.sessions.is_empty() {
+fn prompt_for_session(
+    codex_home: &Path,
+    args: &ResumeArgs,
+    config: &Config,
+) -> Result<SessionSummary> {
+    let opts = build_resume_list_opts(args, config)?;
+    let sessions = list_sessions(codex_home, &opts)?.into_all();
+    if sessions.is_empty() {
         bail!("No recorded sessions available to resume");
     }
 
-    let items: Vec<String> = list
-        .sessions
+    let items: Vec<String> = sessions
         .iter()
         .map(|summary| {
             format!(
                 "{:<18} {:<20} {:<28} {}",
                 summary
                     .updated_at
-                    .map(|dt| format_relative(dt))
+                    .map(format_relative)
                     .unwrap_or_else(|| "unknown".into()),
                 summary.git_branch.as_deref().unwrap_or("-"),
                 summary
@@ -232,12 +495,7 @@ fn prompt_for_session(codex_home: &Path, args: &ResumeArgs) -> Result<SessionSum
                     .as_ref()
                     .map(|path| shorten_path(path, 28))
                     .unwrap_or_else(|| "(unknown)".into()),
-                truncate_preview(
-                    summary
-                        .preview
-                        .as_deref()
-                        .unwrap_or("(no user message yet)")
-                )
+                truncate_preview(summary.display_title().unwrap_or("(no user message yet)"))
             )
         })
         .collect();
@@ -248,18 +506,255 @@ fn prompt_for_session(codex_home: &Path, args: &ResumeArgs) -> Result<SessionSum
         .default(0)
         .interact()?;
 
-    Ok(list.sessions[selection].clone())
+    Ok(sessions[selection].clone())
 }
 
-fn run_info(codex_home: &Path, args: InfoArgs) -> Result<()> {
-    let path = resolve_session_path(codex_home, &args.session)?;
-    let detail = load_session_detail(codex_home, &path)?;
-    print_detail(&detail);
+fn run_search(codex_home: &Path, args: SearchArgs, config: &Config, format: Format) -> Result<()> {
+    let format = format.or_json(args.json);
+    let scope = resolve_scope(&args.scope, config.list.scope)?;
+    let opts = ListOptions {
+        limit: usize::MAX,
+        cursor: None,
+        providers: Vec::new(),
+        scope,
+        remap: config.path_remap(),
+        archived: false,
+        tags: Vec::new(),
+        sources: Vec::new(),
+        include_empty: false,
+        repo: None,
+        branch: None,
+        commit: None,
+    };
+    let sessions = list_sessions(codex_home, &opts)?.into_all();
+    let hits = search_sessions(
+        &sessions,
+        &args.query,
+        args.limit.max(1),
+        args.snippets.max(1),
+    )?;
+
+    match format {
+        Format::Table => {}
+        Format::Json | Format::Jsonl => return output::emit_records(format, &hits),
+        Format::Tsv => {
+            output::print_tsv_row(&["session", "path", "kind", "snippet"]);
+            for hit in &hits {
+                for snippet in &hit.snippets {
+                    output::print_tsv_row(&[
+                        hit.session.id.as_str(),
+                        hit.session.path.to_string_lossy().as_ref(),
+                        snippet.kind.as_str(),
+                        snippet.text.as_str(),
+                    ]);
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    if hits.is_empty() {
+        println!(
+            "{}",
+            format!("No sessions mention \"{}\".", args.query).yellow()
+        );
+        return Ok(());
+    }
+
+    for hit in &hits {
+        let summary = &hit.session;
+        println!(
+            "{}  {}  {}",
+            summary.id.green(),
+            summary
+                .updated_at
+                .map(format_relative)
+                .unwrap_or_else(|| "unknown".into()),
+            summary
+                .cwd
+                .as_ref()
+                .map(|path| shorten_path(path, 40))
+                .unwrap_or_else(|| "(unknown)".into())
+        );
+        if let Some(preview) = summary.display_title() {
+            println!("  {}", truncate_preview(preview).dimmed());
+        }
+        for snippet in &hit.snippets {
+            let (before, matched, after) = snippet.parts();
+            println!(
+                "  [{}] {}{}{}",
+                snippet.kind.cyan(),
+                before,
+                matched.yellow().bold(),
+                after
+            );
+        }
+        println!();
+    }
+
+    if let Some(first) = hits.first() {
+        println!(
+            "To resume the best match, run {}.",
+            first.session.resume_hint()
+        );
+    }
+
     Ok(())
 }
 
-fn print_detail(detail: &SessionDetail) {
+fn run_stats(
+    codex_home: &Path,
+    args: StatsArgs,
+    prices: &PriceTable,
+    config: &Config,
+    format: Format,
+) -> Result<()> {
+    let format = format.or_json(args.json);
+    let scope = resolve_scope(&args.scope, config.list.scope)?;
+    let opts = ListOptions {
+        limit: usize::MAX,
+        cursor: None,
+        providers: Vec::new(),
+        scope,
+        remap: config.path_remap(),
+        archived: false,
+        tags: Vec::new(),
+        sources: Vec::new(),
+        include_empty: false,
+        repo: None,
+        branch: None,
+        commit: None,
+    };
+    let mut sessions = list_sessions(codex_home, &opts)?.into_all();
+    sessions.retain(|summary| {
+        let Some(started) = summary.created_at.or(summary.updated_at) else {
+            return args.since.is_none() && args.until.is_none();
+        };
+        args.since.is_none_or(|since| started >= since)
+            && args.until.is_none_or(|until| started < until)
+    });
+    let usage = stats::compute(&sessions, prices);
+
+    match format {
+        Format::Table => {}
+        Format::Json => return output::emit(format, &usage),
+        // Line-oriented formats get one record per bucket so each line stands alone.
+        Format::Jsonl | Format::Tsv => {
+            let breakdowns = [
+                ("day", &usage.per_day),
+                ("week", &usage.per_week),
+                ("project", &usage.per_project),
+                ("branch", &usage.per_branch),
+                ("provider", &usage.per_provider),
+                ("model", &usage.per_model),
+                ("hour", &usage.busiest_hours),
+            ];
+            let records: Vec<_> = breakdowns
+                .iter()
+                .flat_map(|(dimension, buckets)| {
+                    buckets.iter().map(move |bucket| {
+                        serde_json::json!({
+                            "dimension": dimension,
+                            "key": bucket.key,
+                            "sessions": bucket.sessions,
+                            "turns": bucket.turns,
+                            "tokens": bucket.tokens,
+                        })
+                    })
+                })
+                .collect();
+            return output::emit_records(format, &records);
+        }
+    }
+
+    if usage.sessions == 0 {
+        println!("{}", "No sessions match the requested range.".yellow());
+        return Ok(());
+    }
+
+    println!("Sessions        : {}", usage.sessions.green());
+    println!("Turns           : {}", usage.turns);
+    println!("Tokens          : {}", format_tokens(usage.tokens));
+    if usage.estimated_cost > 0.0 {
+        println!("Estimated cost  : ~${:.2}", usage.estimated_cost);
+    }
+    if let Some(secs) = usage.median_duration_secs {
+        println!("Median duration : {}", format_duration(secs));
+    }
+
+    let top = args.top.max(1);
+    let recent = |buckets: &[stats::Bucket]| buckets[buckets.len().saturating_sub(top)..].to_vec();
+    print_buckets("Day", &recent(&usage.per_day));
+    print_buckets("Week", &recent(&usage.per_week));
+    print_buckets(
+        "Project",
+        &usage.per_project[..usage.per_project.len().min(top)],
+    );
+    print_buckets(
+        "Branch",
+        &usage.per_branch[..usage.per_branch.len().min(top)],
+    );
+    print_buckets(
+        "Provider",
+        &usage.per_provider[..usage.per_provider.len().min(top)],
+    );
+    print_buckets("Model", &usage.per_model[..usage.per_model.len().min(top)]);
+    print_buckets(
+        "Hour",
+        &usage.busiest_hours[..usage.busiest_hours.len().min(top)],
+    );
+    Ok(())
+}
+
+fn print_buckets(label: &str, buckets: &[stats::Bucket]) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        Cell::new(label),
+        Cell::new("Sessions"),
+        Cell::new("Turns"),
+        Cell::new("Tokens"),
+    ]);
+    for bucket in buckets {
+        table.add_row(vec![
+            Cell::new(&bucket.key),
+            Cell::new(bucket.sessions),
+            Cell::new(bucket.turns),
+            Cell::new(format_tokens(bucket.tokens)),
+        ]);
+    }
+    println!();
+    println!("{table}");
+}
+
+pub(crate) fn format_duration(secs: i64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+fn run_info(codex_home: &Path, args: InfoArgs, prices: &PriceTable, format: Format) -> Result<()> {
+    let path = resolve_session_path(codex_home, &args.session)?;
+    let mut detail = load_session_detail(codex_home, &path)?;
+    detail.summary.git_status = GitChecker::default().status(&detail.summary);
+    if format.is_table() {
+        print_detail(&detail, prices);
+        Ok(())
+    } else {
+        output::emit(format, &detail)
+    }
+}
+
+fn print_detail(detail: &SessionDetail, prices: &PriceTable) {
     println!("Session : {}", detail.summary.id.green());
+    if let Some(title) = detail.summary.title.as_ref() {
+        println!("Title   : {title}");
+    }
     println!("Path    : {}", detail.summary.path.display());
     if let Some(cwd) = detail.summary.cwd.as_ref() {
         println!("CWD     : {}", cwd.display());
@@ -267,8 +762,39 @@ fn print_detail(detail: &SessionDetail) {
     if let Some(provider) = detail.summary.provider.as_ref() {
         println!("Provider: {provider}");
     }
-    if let Some(branch) = detail.git_branch.as_ref() {
-        println!("Git     : {branch}");
+    if let Some(context) = detail.turn_context.as_ref() {
+        println!("Model   : {}", context.model_label());
+        if let Some(policy) = context.approval_policy.as_deref() {
+            println!("Approval: {policy}");
+        }
+        println!("Sandbox : {}", context.sandbox_label());
+    } else if let Some(model) = detail.summary.model.as_ref() {
+        println!("Model   : {model}");
+    }
+    if let Some(repo) = detail.summary.git_repository.as_ref() {
+        println!("Repo    : {repo}");
+    }
+    if detail.git_branch.is_some() || detail.summary.git_commit.is_some() {
+        let mut git = detail
+            .git_branch
+            .clone()
+            .unwrap_or_else(|| "(detached)".into());
+        if let Some(commit) = detail.summary.git_commit.as_deref() {
+            git.push_str(&format!(" @ {}", commit.get(..12).unwrap_or(commit)));
+        }
+        match detail.summary.git_status {
+            Some(status) if status.branch_missing && status.commit_diverged => println!(
+                "Git     : {git} {}",
+                "(branch deleted, commit not in HEAD)".yellow()
+            ),
+            Some(status) if status.branch_missing => {
+                println!("Git     : {git} {}", "(branch deleted)".yellow())
+            }
+            Some(status) if status.commit_diverged => {
+                println!("Git     : {git} {}", "(commit not in HEAD)".yellow())
+            }
+            _ => println!("Git     : {git}"),
+        }
     }
     if let Some(created) = detail.summary.created_at {
         println!("Started : {}", format_relative(created));
@@ -276,35 +802,628 @@ fn print_detail(detail: &SessionDetail) {
     if let Some(updated) = detail.summary.updated_at {
         println!("Updated : {}", format_relative(updated));
     }
-    if let Some(source) = detail.source.as_ref() {
-        println!("Source  : {source:?}");
+    println!("Source  : {}", detail.summary.source);
+    if detail.summary.is_empty() {
+        println!("Status  : {}", "empty (no user message yet)".yellow());
     }
     if let Some(instructions) = detail.instructions.as_ref() {
-        println!("Notes   : {}", truncate_preview(instructions));
+        println!("Instr.  : {}", truncate_preview(instructions));
+    }
+    if !detail.summary.tags.is_empty() {
+        println!("Tags    : {}", detail.summary.tags.join(", "));
+    }
+    if let Some(note) = detail.note.as_ref() {
+        println!("Note    : {note}");
+    }
+    if let Some(tokens) = detail.summary.tokens.as_ref() {
+        println!(
+            "Tokens  : {} total ({} input, {} cached, {} output, {} reasoning)",
+            format_tokens(tokens.total),
+            format_tokens(tokens.input),
+            format_tokens(tokens.cached_input),
+            format_tokens(tokens.output),
+            format_tokens(tokens.reasoning)
+        );
+        match prices.estimate(detail.summary.model.as_deref(), tokens) {
+            Some(cost) => println!("Cost    : ~${cost:.2}"),
+            None => println!("Cost    : unknown (no price configured for this model)"),
+        }
     }
     println!("Resume  : {}", detail.summary.resume_hint());
 }
 
-fn run_delete(codex_home: &Path, args: DeleteArgs) -> Result<()> {
+/// One session changed by a mutating command, as printed by `--format json|jsonl|tsv`.
+#[derive(Debug, Serialize)]
+struct ChangeRecord {
+    session: String,
+    action: &'static str,
+    /// Where the rollout file ended up, when it moved.
+    target: Option<PathBuf>,
+    /// Why the change was skipped, for commands that carry on past failures.
+    error: Option<String>,
+}
+
+impl ChangeRecord {
+    fn new(session: impl Into<String>, action: &'static str, target: Option<PathBuf>) -> Self {
+        Self {
+            session: session.into(),
+            action,
+            target,
+            error: None,
+        }
+    }
+}
+
+fn run_export(codex_home: &Path, args: ExportArgs, config: &Config, format: Format) -> Result<()> {
     let path = resolve_session_path(codex_home, &args.session)?;
     let detail = load_session_detail(codex_home, &path)?;
-    if !args.yes {
-        println!(
-            "Delete session {} recorded at {}?",
-            detail.summary.id.red(),
-            path.display()
+    let opts = ExportOptions {
+        kinds: if args.include.is_empty() {
+            EntryKind::ALL.to_vec()
+        } else {
+            args.include
+        },
+        max_output_lines: (args.max_output_lines > 0).then_some(args.max_output_lines),
+        title: detail.summary.title.clone(),
+    };
+    let output = config.export.resolve(&args.output);
+    if let Some(parent) = output.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory {parent:?}"))?;
+    }
+    export_session_chat(&path, &output, &opts)?;
+    if !format.is_table() {
+        return output::emit(
+            format,
+            &ChangeRecord::new(detail.summary.id, "exported", Some(output)),
         );
+    }
+    println!(
+        "Exported session {} to {}",
+        detail.summary.id.green(),
+        output.display()
+    );
+    Ok(())
+}
+
+fn run_archive(codex_home: &Path, args: ArchiveArgs, format: Format) -> Result<()> {
+    let mut records = Vec::new();
+    for query in &args.sessions {
+        let path = resolve_session_path(codex_home, query)?;
+        let target = archive::archive_session(codex_home, &path)?;
+        if format.is_table() {
+            println!("Archived {} to {}", query.green(), target.display());
+        }
+        records.push(ChangeRecord::new(query, "archived", Some(target)));
+    }
+    if format.is_table() {
+        Ok(())
+    } else {
+        output::emit_records(format, &records)
+    }
+}
+
+fn run_unarchive(codex_home: &Path, args: ArchiveArgs, format: Format) -> Result<()> {
+    let mut records = Vec::new();
+    for query in &args.sessions {
+        let path = resolve_session_path(codex_home, query)?;
+        let target = archive::unarchive_session(codex_home, &path)?;
+        if format.is_table() {
+            println!("Restored {} to {}", query.green(), target.display());
+        }
+        records.push(ChangeRecord::new(query, "unarchived", Some(target)));
+    }
+    if format.is_table() {
+        Ok(())
+    } else {
+        output::emit_records(format, &records)
+    }
+}
+
+fn run_delete(codex_home: &Path, args: DeleteArgs, format: Format) -> Result<()> {
+    let path = resolve_session_path(codex_home, &args.session)?;
+    let detail = load_session_detail(codex_home, &path)?;
+    if !args.yes {
+        let consequence = if args.permanent {
+            "This cannot be undone."
+        } else {
+            "The session will be moved to the trash."
+        };
+        // The prompt goes to stderr, so keep the whole question there too.
         if !Confirm::new()
-            .with_prompt("This cannot be undone. Continue?")
+            .with_prompt(format!(
+                "Delete session {} recorded at {}? {consequence} Continue?",
+                detail.summary.id.red(),
+                path.display()
+            ))
             .default(false)
             .interact()?
         {
-            println!("Aborted");
+            eprintln!("Aborted");
             return Ok(());
         }
     }
-    fs::remove_file(&path)?;
-    println!("Removed session {}", detail.summary.id.red());
+    let id = detail.summary.id;
+    if args.permanent {
+        fs::remove_file(&path)?;
+        if !format.is_table() {
+            return output::emit(format, &ChangeRecord::new(id, "removed", None));
+        }
+        println!("Removed session {}", id.red());
+    } else {
+        let entry = trash::trash_session(codex_home, &path)?;
+        if !format.is_table() {
+            return output::emit(format, &ChangeRecord::new(id, "trashed", Some(entry.file)));
+        }
+        println!(
+            "Moved session {} to the trash. Undo with {}.",
+            id.red(),
+            format!("codex-session trash restore {id}").cyan()
+        );
+    }
+    Ok(())
+}
+
+fn run_trash(codex_home: &Path, args: TrashArgs, format: Format) -> Result<()> {
+    match args.command {
+        TrashCommand::List { json } => {
+            let format = format.or_json(json);
+            let entries = trash::list(codex_home)?;
+            if !format.is_table() {
+                return output::emit_records(format, &entries);
+            }
+            if entries.is_empty() {
+                println!("{}", "The trash is empty.".yellow());
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["Deleted", "Session", "Original location"]);
+            for entry in &entries {
+                table.add_row(vec![
+                    Cell::new(format_relative(entry.deleted_at)),
+                    Cell::new(&entry.id),
+                    Cell::new(entry.original_path.display()),
+                ]);
+            }
+            println!("{table}");
+        }
+        TrashCommand::Restore { sessions } => {
+            let entries = trash::list(codex_home)?;
+            let mut records = Vec::new();
+            for query in &sessions {
+                let entry = entries
+                    .iter()
+                    .find(|entry| entry.id == *query)
+                    .with_context(|| format!("{query} is not in the trash"))?;
+                let target = trash::restore(entry)?;
+                if format.is_table() {
+                    println!("Restored {} to {}", query.green(), target.display());
+                }
+                records.push(ChangeRecord::new(query, "restored", Some(target)));
+            }
+            if !format.is_table() {
+                output::emit_records(format, &records)?;
+            }
+        }
+        TrashCommand::Empty { older_than, yes } => {
+            let entries = trash::list(codex_home)?;
+            let cutoff = older_than.map(|age| cli::age_cutoff(Utc::now(), age));
+            let doomed: Vec<_> = entries
+                .iter()
+                .filter(|entry| cutoff.is_none_or(|cutoff| entry.deleted_at < cutoff))
+                .collect();
+            if doomed.is_empty() {
+                if format.is_table() {
+                    println!("{}", "Nothing to purge.".yellow());
+                } else {
+                    output::emit_records::<ChangeRecord>(format, &[])?;
+                }
+                return Ok(());
+            }
+            if !yes
+                && !Confirm::new()
+                    .with_prompt(format!(
+                        "Permanently delete {} trashed sessions?",
+                        doomed.len()
+                    ))
+                    .default(false)
+                    .interact()?
+            {
+                eprintln!("Aborted");
+                return Ok(());
+            }
+            let mut records = Vec::new();
+            for entry in &doomed {
+                trash::remove(entry)?;
+                records.push(ChangeRecord::new(&entry.id, "purged", None));
+            }
+            if format.is_table() {
+                println!("Purged {} sessions from the trash", doomed.len().red());
+            } else {
+                output::emit_records(format, &records)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_prune(codex_home: &Path, args: PruneArgs, config: &Config, format: Format) -> Result<()> {
+    let mut criteria = prune::PruneCriteria {
+        older_than: args.older_than,
+        smaller_than: args.smaller_than,
+        empty: args.empty,
+        keep_last: args.keep_last,
+    };
+    // Flags replace the configured policy as a whole rather than merging with it.
+    if criteria.is_empty() {
+        criteria = config.prune.criteria()?;
+    }
+    if criteria.is_empty() {
+        bail!(
+            "Refusing to prune every session; pass at least one of --older-than, --smaller-than, --empty or --keep-last, or set a [prune] policy in {}",
+            config.path.display()
+        );
+    }
+    // Scripts never see the candidate table, so they have to opt in explicitly.
+    if !format.is_table() && !args.yes && !args.dry_run {
+        bail!("Machine-readable output needs --yes or --dry-run to prune");
+    }
+
+    let scope = resolve_scope(&args.scope, config.list.scope)?;
+    let opts = ListOptions {
+        limit: usize::MAX,
+        cursor: None,
+        providers: args.providers.clone(),
+        scope,
+        remap: config.path_remap(),
+        archived: false,
+        tags: Vec::new(),
+        sources: args.sources.clone(),
+        // Empty sessions are hidden from listings, so only look at them when asked to.
+        include_empty: criteria.empty,
+        repo: None,
+        branch: None,
+        commit: None,
+    };
+    // Pinned sessions are listed separately and so never pruned.
+    let sessions = list_sessions(codex_home, &opts)?.sessions;
+    let candidates = prune::select(sessions, &criteria, Utc::now());
+    let outcome = if args.archive {
+        "archived"
+    } else if args.permanent {
+        "removed"
+    } else {
+        "trashed"
+    };
+
+    if !format.is_table() {
+        let mut records = Vec::new();
+        for candidate in &candidates {
+            let path = &candidate.summary.path;
+            let mut record = ChangeRecord::new(
+                &candidate.summary.id,
+                if args.dry_run { "would_prune" } else { outcome },
+                None,
+            );
+            if !args.dry_run {
+                let result = if args.archive {
+                    archive::archive_session(codex_home, path).map(Some)
+                } else if args.permanent {
+                    fs::remove_file(path)
+                        .map(|_| None)
+                        .with_context(|| format!("failed to remove {path:?}"))
+                } else {
+                    trash::trash_session(codex_home, path).map(|entry| Some(entry.file))
+                };
+                match result {
+                    Ok(target) => record.target = target,
+                    Err(err) => {
+                        record.action = "skipped";
+                        record.error = Some(format!("{err:#}"));
+                    }
+                }
+            }
+            records.push(record);
+        }
+        return output::emit_records(format, &records);
+    }
+
+    if candidates.is_empty() {
+        println!("{}", "No sessions match the prune criteria.".yellow());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Updated", "CWD", "Size", "Conversation"]);
+    for candidate in &candidates {
+        let summary = &candidate.summary;
+        table.add_row(vec![
+            Cell::new(
+                summary
+                    .updated_at
+                    .map(format_relative)
+                    .unwrap_or_else(|| "unknown".into()),
+            ),
+            Cell::new(
+                summary
+                    .cwd
+                    .as_ref()
+                    .map(|path| shorten_path(path, 28))
+                    .unwrap_or_else(|| "(unknown)".into()),
+            ),
+            Cell::new(format_bytes(candidate.size)),
+            Cell::new(truncate_preview(
+                summary.display_title().unwrap_or("(no user message yet)"),
+            )),
+        ]);
+    }
+    println!("{table}");
+
+    let total: u64 = candidates.iter().map(|candidate| candidate.size).sum();
+    let outcome = if args.archive {
+        "archived"
+    } else if args.permanent {
+        "removed for good"
+    } else {
+        "moved to the trash"
+    };
+    println!(
+        "{} sessions, {} in total.",
+        candidates.len().red(),
+        format_bytes(total)
+    );
+    if args.dry_run {
+        println!("Dry run; nothing was changed.");
+        return Ok(());
+    }
+    if !args.yes
+        && !Confirm::new()
+            .with_prompt(format!(
+                "Prune {} sessions? They will be {outcome}.",
+                candidates.len()
+            ))
+            .default(false)
+            .interact()?
+    {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    let mut pruned = 0;
+    for candidate in &candidates {
+        let path = &candidate.summary.path;
+        let result = if args.archive {
+            archive::archive_session(codex_home, path).map(|_| ())
+        } else if args.permanent {
+            fs::remove_file(path).with_context(|| format!("failed to remove {path:?}"))
+        } else {
+            trash::trash_session(codex_home, path).map(|_| ())
+        };
+        match result {
+            Ok(()) => pruned += 1,
+            Err(err) => eprintln!("{} {}: {err:#}", "Skipped".red(), candidate.summary.id),
+        }
+    }
+    println!(
+        "Pruned {} of {} sessions ({outcome}).",
+        pruned.green(),
+        candidates.len()
+    );
+    Ok(())
+}
+
+fn run_tag(codex_home: &Path, args: TagArgs, format: Format) -> Result<()> {
+    let mut store = AnnotationStore::load(codex_home)?;
+    match args.command {
+        TagCommand::Add { session, tags } => {
+            let id = session_id(codex_home, &session)?;
+            let added = store.add_tags(&id, &tags);
+            store.save()?;
+            if !format.is_table() {
+                return output::emit(
+                    format,
+                    &serde_json::json!({ "session": id, "added": added }),
+                );
+            }
+            if added.is_empty() {
+                println!("{} already has those tags", id.green());
+            } else {
+                println!("Tagged {} with {}", id.green(), added.join(", ").cyan());
+            }
+        }
+        TagCommand::Remove { session, tags } => {
+            let id = session_id(codex_home, &session)?;
+            let removed = store.remove_tags(&id, &tags);
+            store.save()?;
+            if !format.is_table() {
+                return output::emit(
+                    format,
+                    &serde_json::json!({ "session": id, "removed": removed }),
+                );
+            }
+            if removed.is_empty() {
+                println!("{} has none of those tags", id.green());
+            } else {
+                println!("Removed {} from {}", removed.join(", ").cyan(), id.green());
+            }
+        }
+        TagCommand::List => {
+            let counts = store.tag_counts();
+            if !format.is_table() {
+                let records: Vec<_> = counts
+                    .iter()
+                    .map(|(tag, count)| serde_json::json!({ "tag": tag, "sessions": count }))
+                    .collect();
+                return output::emit_records(format, &records);
+            }
+            if counts.is_empty() {
+                println!("{}", "No sessions are tagged yet.".yellow());
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["Tag", "Sessions"]);
+            for (tag, count) in &counts {
+                table.add_row(vec![Cell::new(tag), Cell::new(count)]);
+            }
+            println!("{table}");
+        }
+    }
+    Ok(())
+}
+
+fn run_note(codex_home: &Path, args: NoteArgs, format: Format) -> Result<()> {
+    let mut store = AnnotationStore::load(codex_home)?;
+    let id = session_id(codex_home, &args.session)?;
+    let message = if args.clear {
+        store.set_note(&id, None);
+        store.save()?;
+        Some("Cleared the note on")
+    } else if let Some(text) = args.text {
+        store.set_note(&id, Some(text));
+        store.save()?;
+        Some("Saved the note on")
+    } else {
+        None
+    };
+    let note = store.get(&id).and_then(|entry| entry.note.as_deref());
+    if !format.is_table() {
+        return output::emit(format, &serde_json::json!({ "session": id, "note": note }));
+    }
+    match (message, note) {
+        (Some(message), _) => println!("{message} {}", id.green()),
+        (None, Some(note)) => println!("{note}"),
+        (None, None) => println!("{}", format!("{id} has no note.").yellow()),
+    }
+    Ok(())
+}
+
+fn run_rename(codex_home: &Path, args: RenameArgs, format: Format) -> Result<()> {
+    let mut store = AnnotationStore::load(codex_home)?;
+    let id = session_id(codex_home, &args.session)?;
+    let title = if args.clear { None } else { args.title };
+    store.set_title(&id, title.clone());
+    store.save()?;
+    if !format.is_table() {
+        return output::emit(
+            format,
+            &serde_json::json!({ "session": id, "title": title }),
+        );
+    }
+    match title.as_deref() {
+        Some(title) => println!("Renamed {} to {}", id.green(), title.cyan()),
+        None => println!("Cleared the title of {}", id.green()),
+    }
+    Ok(())
+}
+
+fn run_pin(codex_home: &Path, args: ArchiveArgs, pinned: bool, format: Format) -> Result<()> {
+    let mut store = AnnotationStore::load(codex_home)?;
+    let mut records = Vec::new();
+    for query in &args.sessions {
+        let id = session_id(codex_home, query)?;
+        let changed = store.set_pinned(&id, pinned);
+        let action = match (changed, pinned) {
+            (true, true) => "pinned",
+            (true, false) => "unpinned",
+            (false, _) => "unchanged",
+        };
+        records.push(ChangeRecord::new(id, action, None));
+    }
+    store.save()?;
+    if !format.is_table() {
+        return output::emit_records(format, &records);
+    }
+    for record in &records {
+        match (record.action, pinned) {
+            ("pinned", _) => println!("Pinned {}", record.session.green()),
+            ("unpinned", _) => println!("Unpinned {}", record.session.green()),
+            (_, true) => println!("{} is already pinned", record.session.green()),
+            (_, false) => println!("{} is not pinned", record.session.green()),
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a session id or path to the id annotations are keyed by.
+fn session_id(codex_home: &Path, query: &str) -> Result<String> {
+    let path = resolve_session_path(codex_home, query)?;
+    Ok(load_session_detail(codex_home, &path)?.summary.id)
+}
+
+fn run_index(codex_home: &Path, args: IndexArgs, format: Format) -> Result<()> {
+    match args.command {
+        IndexCommand::Rebuild => {
+            let stats = session_index::rebuild(codex_home)?;
+            let index = session_index::index_path(codex_home);
+            if !format.is_table() {
+                return output::emit(
+                    format,
+                    &serde_json::json!({
+                        "sessions": stats.sessions,
+                        "scanned_files": stats.scanned_files,
+                        "index": index,
+                    }),
+                );
+            }
+            println!(
+                "Indexed {} sessions from {} rollout files into {}",
+                stats.sessions.green(),
+                stats.scanned_files,
+                index.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Handle the `config` subcommands that only need the file's location.
+fn run_config_file(
+    codex_home: &Path,
+    path: &Path,
+    command: &ConfigCommand,
+    format: Format,
+) -> Result<()> {
+    match command {
+        ConfigCommand::Show => unreachable!("config show needs the parsed config"),
+        ConfigCommand::Path if format.is_table() => println!("{}", path.display()),
+        ConfigCommand::Path => output::emit(format, &serde_json::json!({ "path": path }))?,
+        ConfigCommand::Edit => {
+            config::ensure_exists(path)?;
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| String::from("vi"));
+            let mut words = editor.split_whitespace();
+            let program = words.next().unwrap_or("vi");
+            loop {
+                let status = ProcessCommand::new(program)
+                    .args(words.clone())
+                    .arg(path)
+                    .status()
+                    .with_context(|| format!("failed to spawn {program}"))?;
+                if !status.success() {
+                    bail!("{program} exited with status {status}");
+                }
+                // Surface mistakes now rather than on the next unrelated command.
+                let Err(err) = config::load(codex_home, Some(path)) else {
+                    break;
+                };
+                eprintln!("{} {err:#}", "Error:".red());
+                if !Confirm::new()
+                    .with_prompt("Edit the file again?")
+                    .default(true)
+                    .interact()?
+                {
+                    bail!("{} still has errors", path.display());
+                }
+            }
+            println!("Saved {}", path.display().green());
+        }
+    }
     Ok(())
 }
 
@@ -318,6 +1437,49 @@ pub(crate) fn truncate_preview(text: &str) -> String {
     }
 }
 
+pub(crate) fn format_tokens(count: u64) -> String {
+    if count >= 1_000_000 {
+        format!("{:.1}M", count as f64 / 1_000_000.0)
+    } else if count >= 1_000 {
+        format!("{:.1}k", count as f64 / 1_000.0)
+    } else {
+        count.to_string()
+    }
+}
+
+pub(crate) fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        String::from("-")
+    } else {
+        tags.join(", ")
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Total tokens, with the estimated cost when the model has a known price.
+pub(crate) fn format_usage(summary: &SessionSummary, prices: &PriceTable) -> String {
+    let Some(tokens) = summary.tokens.as_ref() else {
+        return String::from("-");
+    };
+    match prices.estimate(summary.model.as_deref(), tokens) {
+        Some(cost) => format!("{} (${cost:.2})", format_tokens(tokens.total)),
+        None => format_tokens(tokens.total),
+    }
+}
+
 pub(crate) fn format_relative(dt: DateTime<Utc>) -> String {
     let now = Utc::now();
     let ht = HumanTime::from(dt - now);
@@ -343,15 +1505,3 @@ pub(crate) fn truncate_left(text: &str, max_chars: usize) -> String {
         format!("…{tail}")
     }
 }
-
-fn resume_session(codex_bin: &str, session_id: &str) -> Result<()> {
-    let status = ProcessCommand::new(codex_bin)
-        .arg("resume")
-        .arg(session_id)
-        .status()
-        .with_context(|| format!("failed to spawn {codex_bin}"))?;
-    if !status.success() {
-        bail!("codex exited with status {status}");
-    }
-    Ok(())
-}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How subcommands print their results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable tables and messages.
+    #[default]
+    Table,
+    /// A single pretty-printed JSON document.
+    Json,
+    /// One compact JSON object per line.
+    Jsonl,
+    /// Tab-separated values with a header row.
+    Tsv,
+}
+
+impl Format {
+    pub fn is_table(self) -> bool {
+        self == Format::Table
+    }
+
+    /// Let a subcommand's older `--json` flag override the global format.
+    pub fn or_json(self, json: bool) -> Self {
+        if json { Format::Json } else { self }
+    }
+}
+
+/// Print one result document.
+pub fn emit<T: Serialize>(format: Format, value: &T) -> Result<()> {
+    match format {
+        Format::Table | Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        Format::Jsonl => println!("{}", serde_json::to_string(value)?),
+        Format::Tsv => emit_records(format, std::slice::from_ref(value))?,
+    }
+    Ok(())
+}
+
+/// Print a list of results: an array for `json`, one line per record for `jsonl` and `tsv`.
+///
+/// TSV columns are the fields of the first record; nested values are flattened
+/// to comma-separated lists or compact JSON.
+pub fn emit_records<T: Serialize>(format: Format, records: &[T]) -> Result<()> {
+    match format {
+        Format::Table | Format::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        Format::Jsonl => {
+            for record in records {
+                println!("{}", serde_json::to_string(record)?);
+            }
+        }
+        Format::Tsv => {
+            let rows = records
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            let header: Vec<String> = match rows.first() {
+                Some(Value::Object(fields)) => fields.keys().cloned().collect(),
+                Some(_) => vec![String::from("value")],
+                None => return Ok(()),
+            };
+            print_tsv_row(&header);
+            for row in &rows {
+                let cells: Vec<String> = match row {
+                    Value::Object(fields) => header
+                        .iter()
+                        .map(|key| fields.get(key).map(tsv_cell).unwrap_or_default())
+                        .collect(),
+                    other => vec![tsv_cell(other)],
+                };
+                print_tsv_row(&cells);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print one tab-separated line, escaping tabs, newlines and backslashes in each field.
+pub fn print_tsv_row<S: AsRef<str>>(fields: &[S]) {
+    let escaped: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            field
+                .as_ref()
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        })
+        .collect();
+    println!("{}", escaped.join("\t"));
+}
+
+fn tsv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) if items.iter().all(|item| !item.is_object()) => {
+            items.iter().map(tsv_cell).collect::<Vec<_>>().join(",")
+        }
+        other => other.to_string(),
+    }
+}
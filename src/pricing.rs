@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::session_store::TokenTotals;
+
+/// Built-in list prices in USD per million tokens; override them in the config file.
+const BUILTIN_PRICES: &[(&str, ModelPrice)] = &[
+    ("gpt-5", ModelPrice::new(1.25, 0.125, 10.0)),
+    ("gpt-5-codex", ModelPrice::new(1.25, 0.125, 10.0)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 0.025, 2.0)),
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.005, 0.4)),
+    ("gpt-4.1", ModelPrice::new(2.0, 0.5, 8.0)),
+    ("o3", ModelPrice::new(2.0, 0.5, 8.0)),
+    ("o4-mini", ModelPrice::new(1.1, 0.275, 4.4)),
+    ("codex-mini-latest", ModelPrice::new(1.5, 0.375, 6.0)),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPrice {
+    pub input: f64,
+    /// Defaults to the regular input price when the model has no cache discount.
+    #[serde(default)]
+    pub cached_input: Option<f64>,
+    pub output: f64,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, cached_input: f64, output: f64) -> Self {
+        Self {
+            input,
+            cached_input: Some(cached_input),
+            output,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PriceTable {
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl PriceTable {
+    pub fn new(overrides: &HashMap<String, ModelPrice>) -> Self {
+        let mut prices: HashMap<String, ModelPrice> = BUILTIN_PRICES
+            .iter()
+            .map(|(model, price)| (model.to_string(), *price))
+            .collect();
+        for (model, price) in overrides {
+            prices.insert(model.to_ascii_lowercase(), *price);
+        }
+        Self { prices }
+    }
+
+    /// Find the price for a model, falling back to the longest matching prefix
+    /// so dated snapshots like `gpt-5-2025-08-07` resolve to `gpt-5`.
+    pub fn lookup(&self, model: &str) -> Option<&ModelPrice> {
+        let model = model.to_ascii_lowercase();
+        self.prices.get(&model).or_else(|| {
+            self.prices
+                .iter()
+                .filter(|(name, _)| model.starts_with(name.as_str()))
+                .max_by_key(|(name, _)| name.len())
+                .map(|(_, price)| price)
+        })
+    }
+
+    /// Estimated cost in USD, or `None` when the model has no known price.
+    pub fn estimate(&self, model: Option<&str>, tokens: &TokenTotals) -> Option<f64> {
+        let price = self.lookup(model?)?;
+        let cached = tokens.cached_input.min(tokens.input);
+        let uncached = tokens.input - cached;
+        let cost = uncached as f64 * price.input
+            + cached as f64 * price.cached_input.unwrap_or(price.input)
+            + tokens.output as f64 * price.output;
+        Some(cost / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(overrides: &[(&str, ModelPrice)]) -> PriceTable {
+        let overrides = overrides
+            .iter()
+            .map(|(model, price)| (model.to_string(), *price))
+            .collect();
+        PriceTable::new(&overrides)
+    }
+
+    #[test]
+    fn lookup_prefers_an_exact_match() {
+        let prices = table(&[]);
+        assert_eq!(
+            prices.lookup("gpt-5-mini"),
+            Some(&ModelPrice::new(0.25, 0.025, 2.0))
+        );
+        assert_eq!(
+            prices.lookup("GPT-5"),
+            Some(&ModelPrice::new(1.25, 0.125, 10.0))
+        );
+    }
+
+    #[test]
+    fn lookup_falls_back_to_the_longest_prefix() {
+        let prices = table(&[]);
+        assert_eq!(
+            prices.lookup("gpt-5-mini-2025-08-07"),
+            Some(&ModelPrice::new(0.25, 0.025, 2.0))
+        );
+        assert_eq!(
+            prices.lookup("gpt-5-2025-08-07"),
+            Some(&ModelPrice::new(1.25, 0.125, 10.0))
+        );
+        assert_eq!(prices.lookup("claude-unknown"), None);
+    }
+
+    #[test]
+    fn overrides_replace_builtin_prices_case_insensitively() {
+        let custom = ModelPrice {
+            input: 3.0,
+            cached_input: None,
+            output: 12.0,
+        };
+        let prices = table(&[("GPT-5", custom), ("my-model", custom)]);
+        assert_eq!(prices.lookup("gpt-5-2025-08-07"), Some(&custom));
+        assert_eq!(prices.lookup("my-model-v2"), Some(&custom));
+    }
+
+    #[test]
+    fn estimate_bills_cached_input_separately() {
+        let prices = table(&[]);
+        let tokens = TokenTotals {
+            input: 1_000_000,
+            cached_input: 400_000,
+            output: 100_000,
+            reasoning: 0,
+            total: 1_100_000,
+        };
+        let cost = prices.estimate(Some("gpt-5"), &tokens).unwrap();
+        // 600k uncached at 1.25, 400k cached at 0.125 and 100k output at 10.0.
+        assert!((cost - (0.75 + 0.05 + 1.0)).abs() < 1e-9);
+        assert_eq!(prices.estimate(None, &tokens), None);
+        assert_eq!(prices.estimate(Some("unknown"), &tokens), None);
+    }
+
+    #[test]
+    fn estimate_uses_the_input_price_without_a_cache_discount() {
+        let custom = ModelPrice {
+            input: 2.0,
+            cached_input: None,
+            output: 0.0,
+        };
+        let prices = table(&[("local", custom)]);
+        let tokens = TokenTotals {
+            input: 1_000_000,
+            cached_input: 1_000_000,
+            ..TokenTotals::default()
+        };
+        assert_eq!(prices.estimate(Some("local"), &tokens), Some(2.0));
+    }
+}
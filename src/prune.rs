@@ -0,0 +1,88 @@
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cli::age_cutoff;
+use crate::session_store::{SessionSummary, parse_timestamp_uuid_from_filename};
+
+/// Conditions a session must meet to be pruned; every criterion that is set must match.
+#[derive(Debug, Clone, Default)]
+pub struct PruneCriteria {
+    /// Only sessions whose last activity is older than this.
+    pub older_than: Option<Duration>,
+    /// Only rollouts smaller than this many bytes.
+    pub smaller_than: Option<u64>,
+    /// Only sessions that never received a user message at all.
+    pub empty: bool,
+    /// Always keep this many of the most recently active sessions of each project.
+    pub keep_last: Option<usize>,
+}
+
+impl PruneCriteria {
+    pub fn is_empty(&self) -> bool {
+        self.older_than.is_none()
+            && self.smaller_than.is_none()
+            && !self.empty
+            && self.keep_last.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PruneCandidate {
+    pub summary: SessionSummary,
+    /// Size of the rollout file in bytes.
+    pub size: u64,
+}
+
+/// Pick the sessions matching `criteria`, most recently active first.
+pub fn select(
+    mut sessions: Vec<SessionSummary>,
+    criteria: &PruneCriteria,
+    now: DateTime<Utc>,
+) -> Vec<PruneCandidate> {
+    sessions.sort_by_key(|summary| Reverse(last_active(summary)));
+    let cutoff = criteria.older_than.map(|age| age_cutoff(now, age));
+    let mut kept_per_project: HashMap<Option<PathBuf>, usize> = HashMap::new();
+    let mut candidates = Vec::new();
+
+    for summary in sessions {
+        if let Some(keep) = criteria.keep_last {
+            let kept = kept_per_project.entry(summary.cwd.clone()).or_default();
+            if *kept < keep {
+                *kept += 1;
+                continue;
+            }
+        }
+        if let Some(cutoff) = cutoff
+            && last_active(&summary).is_none_or(|active| active >= cutoff)
+        {
+            continue;
+        }
+        // Judged by the turn count of the whole rollout, never by the preview, which only
+        // covers the first few records and skips instruction-like messages.
+        if criteria.empty && !summary.is_empty() {
+            continue;
+        }
+        // A rollout that vanished since listing has nothing left to reclaim.
+        let Ok(meta) = fs::metadata(&summary.path) else {
+            continue;
+        };
+        let size = meta.len();
+        if criteria.smaller_than.is_some_and(|limit| size >= limit) {
+            continue;
+        }
+        candidates.push(PruneCandidate { summary, size });
+    }
+    candidates
+}
+
+/// Last recorded activity, falling back to the start time encoded in the rollout filename.
+fn last_active(summary: &SessionSummary) -> Option<DateTime<Utc>> {
+    summary.updated_at.or_else(|| {
+        let name = summary.path.file_name()?.to_str()?;
+        let (ts, _) = parse_timestamp_uuid_from_filename(name)?;
+        DateTime::from_timestamp(ts.unix_timestamp(), 0)
+    })
+}
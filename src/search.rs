@@ -0,0 +1,261 @@
+use anyhow::{Context, Result};
+use codex_protocol::protocol::{RolloutItem, RolloutLine};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::session_store::{SessionSummary, chat_entry_from_item};
+
+/// Bytes of surrounding context kept on each side of a match.
+const SNIPPET_CONTEXT: usize = 40;
+const ELLIPSIS: &str = "…";
+/// Records whose searched text is copied unchanged from a JSON string field.
+const VERBATIM_TYPES: [&str; 4] = [
+    r#""type":"message""#,
+    r#""type":"reasoning""#,
+    r#""type":"function_call_output""#,
+    r#""type":"custom_tool_call_output""#,
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub session: SessionSummary,
+    pub snippets: Vec<Snippet>,
+}
+
+/// A single-line window of transcript text around one match.
+#[derive(Debug, Clone, Serialize)]
+pub struct Snippet {
+    pub kind: String,
+    pub text: String,
+    pub match_start: usize,
+    pub match_end: usize,
+}
+
+impl Snippet {
+    /// Split the snippet into the text before, inside and after the match.
+    pub fn parts(&self) -> (&str, &str, &str) {
+        (
+            &self.text[..self.match_start],
+            &self.text[self.match_start..self.match_end],
+            &self.text[self.match_end..],
+        )
+    }
+}
+
+/// Scan each session's full transcript, keeping only sessions with at least one match.
+pub fn search_sessions(
+    sessions: &[SessionSummary],
+    query: &str,
+    max_hits: usize,
+    max_snippets: usize,
+) -> Result<Vec<SearchHit>> {
+    let mut hits = Vec::new();
+    for session in sessions {
+        if hits.len() >= max_hits {
+            break;
+        }
+        let snippets = search_transcript(&session.path, query, max_snippets)?;
+        if !snippets.is_empty() {
+            hits.push(SearchHit {
+                session: session.clone(),
+                snippets,
+            });
+        }
+    }
+    Ok(hits)
+}
+
+/// Case-insensitively search every message, tool call and tool output in a rollout.
+pub fn search_transcript(path: &Path, query: &str, max_snippets: usize) -> Result<Vec<Snippet>> {
+    // Folded char by char like the transcript text; `str::to_lowercase` would treat a
+    // trailing sigma differently and miss matches.
+    let needle: String = query.trim().chars().flat_map(char::to_lowercase).collect();
+    let mut snippets = Vec::new();
+    if needle.is_empty() || max_snippets == 0 {
+        return Ok(snippets);
+    }
+    // JSON keeps plain ASCII verbatim, so such needles can be ruled out before parsing
+    // records that store the searched text as is. Lowercasing the whole line still folds
+    // characters such as the Kelvin sign that lowercase to ASCII.
+    let prefilter = needle
+        .chars()
+        .all(|c| c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\');
+
+    let file = File::open(path).with_context(|| format!("failed to open session file {path:?}"))?;
+    let reader = BufReader::new(file);
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        // Only response items are searched; skip events and metadata without parsing them.
+        if trimmed.is_empty() || !trimmed.contains(r#""type":"response_item""#) {
+            continue;
+        }
+        if prefilter && is_verbatim(trimmed) && !trimmed.to_lowercase().contains(&needle) {
+            continue;
+        }
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(trimmed) else {
+            continue;
+        };
+        let RolloutItem::ResponseItem(item) = rollout_line.item else {
+            continue;
+        };
+        let Some(entry) = chat_entry_from_item(item) else {
+            continue;
+        };
+        collect_snippets(
+            &entry.label(),
+            entry.text(),
+            &needle,
+            max_snippets,
+            &mut snippets,
+        );
+        if snippets.len() >= max_snippets {
+            break;
+        }
+    }
+    Ok(snippets)
+}
+
+fn collect_snippets(
+    kind: &str,
+    text: &str,
+    needle: &str,
+    max_snippets: usize,
+    out: &mut Vec<Snippet>,
+) {
+    // Lowercasing can change a character's length, so remember where each byte of the
+    // lowercased text came from in `text`.
+    let mut haystack = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+    for (offset, c) in text.char_indices() {
+        for lower in c.to_lowercase() {
+            haystack.push(lower);
+            origins.extend(std::iter::repeat_n(
+                (offset, offset + c.len_utf8()),
+                lower.len_utf8(),
+            ));
+        }
+    }
+    let mut searched = 0;
+    let mut from = 0;
+    while out.len() < max_snippets {
+        let Some(found) = haystack[searched..].find(needle) else {
+            break;
+        };
+        let found = searched + found;
+        searched = found + needle.len();
+        let match_start = origins[found].0;
+        let match_end = origins[searched - 1].1;
+        // Matches inside the context of the previous snippet are not shown again.
+        if match_start < from {
+            continue;
+        }
+        let start = floor_boundary(text, match_start.saturating_sub(SNIPPET_CONTEXT));
+        let end = ceil_boundary(text, (match_end + SNIPPET_CONTEXT).min(text.len()));
+
+        let mut snippet = String::new();
+        if start > 0 {
+            snippet.push_str(ELLIPSIS);
+        }
+        let offset = snippet.len();
+        snippet.extend(text[start..end].chars().map(|c| match c {
+            '\n' | '\r' | '\t' => ' ',
+            other => other,
+        }));
+        if end < text.len() {
+            snippet.push_str(ELLIPSIS);
+        }
+
+        out.push(Snippet {
+            kind: kind.to_string(),
+            text: snippet,
+            match_start: offset + match_start - start,
+            match_end: offset + match_end - start,
+        });
+        from = end;
+    }
+}
+
+fn floor_boundary(text: &str, mut idx: usize) -> usize {
+    while idx > 0 && !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+fn ceil_boundary(text: &str, mut idx: usize) -> usize {
+    while idx < text.len() && !text.is_char_boundary(idx) {
+        idx += 1;
+    }
+    idx
+}
+
+/// Whether a needle missing from the raw line can be ruled out for this record.
+///
+/// Tool calls and web searches are searched as they are displayed (shell argv joined with
+/// spaces, argument JSON unescaped), which is not how they appear in the rollout, and
+/// images are shown with an `[image: …]` label.
+fn is_verbatim(line: &str) -> bool {
+    VERBATIM_TYPES.iter().any(|kind| line.contains(kind)) && !line.contains(r#""input_image""#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippets(text: &str, needle: &str, max: usize) -> Vec<Snippet> {
+        let mut out = Vec::new();
+        collect_snippets("message", text, needle, max, &mut out);
+        out
+    }
+
+    #[test]
+    fn matches_ignore_case_beyond_ascii() {
+        let found = snippets("Grüße aus ÄRGERLICH", "ärger", 5);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].parts(), ("Grüße aus ", "ÄRGER", "LICH"));
+
+        let found = snippets("Привет, МИР", "мир", 5);
+        assert_eq!(found[0].parts(), ("Привет, ", "МИР", ""));
+    }
+
+    #[test]
+    fn matches_map_back_when_lowercasing_changes_the_length() {
+        // `İ` lowercases to `i` plus a combining dot, which is longer in UTF-8.
+        let found = snippets("İİ ABC", "abc", 5);
+        assert_eq!(found[0].parts(), ("İİ ", "ABC", ""));
+
+        let found = snippets("İ", "i", 5);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].parts(), ("", "İ", ""));
+    }
+
+    #[test]
+    fn snippets_trim_long_text_and_respect_the_limit() {
+        let text = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let found = snippets(&text, "needle", 5);
+        assert_eq!(found.len(), 1);
+        let (before, matched, after) = found[0].parts();
+        assert_eq!(before, format!("{ELLIPSIS}{}", "a".repeat(SNIPPET_CONTEXT)));
+        assert_eq!(matched, "needle");
+        assert_eq!(after, format!("{}{ELLIPSIS}", "b".repeat(SNIPPET_CONTEXT)));
+
+        let spaced = format!("x{}", " ".repeat(100)).repeat(3);
+        assert_eq!(snippets(&spaced, "x", 2).len(), 2);
+    }
+
+    #[test]
+    fn verbatim_records_exclude_tool_calls_and_images() {
+        assert!(is_verbatim(
+            r#"{"type":"response_item","payload":{"type":"message"}}"#
+        ));
+        assert!(!is_verbatim(
+            r#"{"type":"response_item","payload":{"type":"function_call"}}"#
+        ));
+        assert!(!is_verbatim(
+            r#"{"type":"response_item","payload":{"type":"message","content":[{"type":"input_image"}]}}"#
+        ));
+    }
+}
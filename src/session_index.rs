@@ -0,0 +1,307 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::session_store::{
+    SESSIONS_SUBDIR, SessionSummary, parse_timestamp_uuid_from_filename, summarize_session,
+};
+
+pub const STATE_SUBDIR: &str = "codex-session";
+const INDEX_FILE: &str = "index.json";
+/// Bump whenever `SessionSummary` changes shape so stale caches are discarded.
+const INDEX_VERSION: u32 = 8;
+
+/// On-disk cache of session summaries keyed by rollout path.
+///
+/// Entries are invalidated whenever the rollout's size or modification time
+/// changes, so unchanged files are never re-parsed.
+pub struct SessionIndex {
+    path: PathBuf,
+    entries: HashMap<PathBuf, IndexEntry>,
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    path: PathBuf,
+    size: u64,
+    modified: Option<DateTime<Utc>>,
+    summary: Option<SessionSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    entries: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RebuildStats {
+    pub scanned_files: usize,
+    pub sessions: usize,
+}
+
+impl SessionIndex {
+    /// Load the index from disk, starting empty if it is missing, corrupt or outdated.
+    pub fn load(codex_home: &Path) -> Self {
+        let path = index_path(codex_home);
+        let entries = read_index(&path)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.path.clone(), entry))
+            .collect();
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    fn empty(codex_home: &Path) -> Self {
+        Self {
+            path: index_path(codex_home),
+            entries: HashMap::new(),
+            dirty: true,
+        }
+    }
+
+    /// Return the cached summary for `rollout`, re-parsing it only when the file changed.
+    pub fn summarize(&mut self, rollout: &Path) -> Result<Option<SessionSummary>> {
+        let (size, modified) = fingerprint(rollout)?;
+        if let Some(entry) = self.entries.get(rollout)
+            && entry.size == size
+            && entry.modified == modified
+        {
+            return Ok(entry.summary.clone());
+        }
+
+        let summary = summarize_session(rollout)?;
+        self.entries.insert(
+            rollout.to_path_buf(),
+            IndexEntry {
+                path: rollout.to_path_buf(),
+                size,
+                modified,
+                summary: summary.clone(),
+            },
+        );
+        self.dirty = true;
+        Ok(summary)
+    }
+
+    /// Persist the index if anything changed since it was loaded.
+    ///
+    /// Entries for rollouts that were deleted, trashed, archived or pruned since they
+    /// were cached are dropped first, so the file does not grow without bound.
+    pub fn save(&mut self) -> Result<()> {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| path.exists());
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("unable to create index directory {parent:?}"))?;
+        }
+
+        let mut entries: Vec<IndexEntry> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let payload = IndexFile {
+            version: INDEX_VERSION,
+            entries,
+        };
+
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let mut writer = BufWriter::new(
+                File::create(&tmp_path)
+                    .with_context(|| format!("failed to create index file {tmp_path:?}"))?,
+            );
+            serde_json::to_writer(&mut writer, &payload)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("failed to write index file {:?}", self.path))?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Discard the cached index and re-parse every rollout under the sessions directory.
+pub fn rebuild(codex_home: &Path) -> Result<RebuildStats> {
+    let mut index = SessionIndex::empty(codex_home);
+    let mut stats = RebuildStats::default();
+    let root = codex_home.join(SESSIONS_SUBDIR);
+    if root.exists() {
+        for entry in WalkDir::new(&root).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let is_rollout = entry
+                .file_name()
+                .to_str()
+                .and_then(parse_timestamp_uuid_from_filename)
+                .is_some();
+            if !is_rollout {
+                continue;
+            }
+            stats.scanned_files += 1;
+            if index.summarize(entry.path())?.is_some() {
+                stats.sessions += 1;
+            }
+        }
+    }
+    index.save()?;
+    Ok(stats)
+}
+
+pub fn index_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(STATE_SUBDIR).join(INDEX_FILE)
+}
+
+fn read_index(path: &Path) -> Option<Vec<IndexEntry>> {
+    let file = File::open(path).ok()?;
+    let parsed: IndexFile = serde_json::from_reader(BufReader::new(file)).ok()?;
+    if parsed.version != INDEX_VERSION {
+        return None;
+    }
+    Some(parsed.entries)
+}
+
+fn fingerprint(path: &Path) -> Result<(u64, Option<DateTime<Utc>>)> {
+    let meta = fs::metadata(path).with_context(|| format!("failed to stat {path:?}"))?;
+    let modified = meta.modified().ok().map(DateTime::<Utc>::from);
+    Ok((meta.len(), modified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_store::SourceKind;
+
+    /// A fresh directory under the system temp dir, unique to this test run.
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("codex-session-index-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn cached_summary(path: &Path) -> SessionSummary {
+        SessionSummary {
+            id: "cached".to_string(),
+            path: path.to_path_buf(),
+            preview: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+            git_commit: None,
+            git_repository: None,
+            git_status: None,
+            provider: None,
+            model: None,
+            source: SourceKind::default(),
+            tokens: None,
+            turns: 1,
+            size: 0,
+            tags: Vec::new(),
+            pinned: false,
+            title: None,
+            auto_title: None,
+        }
+    }
+
+    /// Cache `summary` for `rollout` as if it had been parsed from the file as it is now.
+    fn seed(index: &mut SessionIndex, rollout: &Path, summary: Option<SessionSummary>) {
+        let (size, modified) = fingerprint(rollout).unwrap();
+        index.entries.insert(
+            rollout.to_path_buf(),
+            IndexEntry {
+                path: rollout.to_path_buf(),
+                size,
+                modified,
+                summary,
+            },
+        );
+    }
+
+    #[test]
+    fn unchanged_rollouts_come_from_the_cache() {
+        let home = scratch("unchanged");
+        let rollout = home.join("rollout.jsonl");
+        fs::write(&rollout, "{}\n").unwrap();
+        let mut index = SessionIndex::load(&home);
+        seed(&mut index, &rollout, Some(cached_summary(&rollout)));
+
+        let summary = index.summarize(&rollout).unwrap();
+        assert_eq!(summary.map(|summary| summary.id).as_deref(), Some("cached"));
+        assert!(!index.dirty);
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn changed_rollouts_are_parsed_again() {
+        let home = scratch("changed");
+        let rollout = home.join("rollout.jsonl");
+        fs::write(&rollout, "{}\n").unwrap();
+        let mut index = SessionIndex::load(&home);
+        seed(&mut index, &rollout, Some(cached_summary(&rollout)));
+
+        // Growing the file changes its size, so the stale summary must not be reused.
+        fs::write(&rollout, "{}\n{}\n").unwrap();
+        assert!(index.summarize(&rollout).unwrap().is_none());
+        assert!(index.dirty);
+        assert_eq!(index.entries[&rollout].size, 6);
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn save_round_trips_and_drops_vanished_rollouts() {
+        let home = scratch("save");
+        let kept = home.join("kept.jsonl");
+        let gone = home.join("gone.jsonl");
+        fs::write(&kept, "{}\n").unwrap();
+        fs::write(&gone, "{}\n").unwrap();
+        let mut index = SessionIndex::load(&home);
+        seed(&mut index, &kept, Some(cached_summary(&kept)));
+        seed(&mut index, &gone, None);
+        fs::remove_file(&gone).unwrap();
+
+        index.dirty = true;
+        index.save().unwrap();
+        assert!(!index.entries.contains_key(&gone));
+
+        let reloaded = SessionIndex::load(&home);
+        assert_eq!(reloaded.entries.len(), 1);
+        assert_eq!(
+            reloaded.entries[&kept]
+                .summary
+                .as_ref()
+                .map(|summary| summary.id.as_str()),
+            Some("cached")
+        );
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn outdated_index_files_are_ignored() {
+        let home = scratch("outdated");
+        let path = index_path(&home);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"version":0,"entries":[]}"#).unwrap();
+        assert!(read_index(&path).is_none());
+        fs::write(&path, "not json").unwrap();
+        assert!(SessionIndex::load(&home).entries.is_empty());
+        fs::remove_dir_all(&home).unwrap();
+    }
+}
@@ -1,20 +1,25 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use codex_protocol::models::{ContentItem, ResponseItem};
+use codex_protocol::models::{
+    ContentItem, LocalShellAction, ReasoningItemContent, ReasoningItemReasoningSummary,
+    ResponseItem, WebSearchAction,
+};
 use codex_protocol::protocol::{
-    EventMsg, RolloutItem, RolloutLine, SessionMetaLine, SessionSource,
+    EventMsg, RolloutItem, RolloutLine, SessionMetaLine, SessionSource, TokenUsage, TurnContextItem,
 };
 use owo_colors::OwoColorize;
 use printpdf::{BuiltinFont, Mm, PdfDocument};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::cmp::Reverse;
+use std::fmt;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
 use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use thiserror::Error;
 use time::format_description::FormatItem;
 use time::macros::format_description;
@@ -22,12 +27,18 @@ use time::{OffsetDateTime, PrimitiveDateTime};
 use uuid::Uuid;
 use walkdir::WalkDir;
 
-const SESSIONS_SUBDIR: &str = "sessions";
+use crate::annotations::AnnotationStore;
+use crate::auto_title::TitleHints;
+use crate::git::{GitStatus, repo_label};
+use crate::session_index::SessionIndex;
+
+pub(crate) const SESSIONS_SUBDIR: &str = "sessions";
+pub(crate) const ARCHIVED_SUBDIR: &str = "archived_sessions";
 const MAX_SCAN_FILES: usize = 10_000;
 const HEAD_RECORD_LIMIT: usize = 10;
-const INTERACTIVE_SOURCES: &[SessionSource] = &[SessionSource::Cli, SessionSource::VSCode];
+const DEFAULT_MAX_OUTPUT_LINES: usize = 50;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionSummary {
     pub id: String,
     pub path: PathBuf,
@@ -36,23 +47,277 @@ pub struct SessionSummary {
     pub updated_at: Option<DateTime<Utc>>,
     pub cwd: Option<PathBuf>,
     pub git_branch: Option<String>,
+    /// Commit checked out when the session started.
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    /// Remote URL of the repository the session ran in.
+    #[serde(default)]
+    pub git_repository: Option<String>,
+    /// Whether the recorded branch and commit still exist locally; checked on demand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_status: Option<GitStatus>,
     pub provider: Option<String>,
+    pub model: Option<String>,
+    /// What started the session: the CLI, the VS Code extension, `codex exec` or an MCP client.
+    #[serde(default)]
+    pub source: SourceKind,
+    pub tokens: Option<TokenTotals>,
+    /// Number of user messages in the rollout.
+    pub turns: usize,
+    /// Size of the rollout file in bytes.
+    #[serde(default)]
+    pub size: u64,
+    /// Tags from the annotation store; never cached in the index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Pinned sessions are listed above all others.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Human title from the annotation store, shown instead of the preview.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Label derived offline from the transcript.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_title: Option<String>,
 }
 
 impl SessionSummary {
+    /// The user-assigned title, then the derived one, then the first user message.
+    pub fn display_title(&self) -> Option<&str> {
+        self.title
+            .as_deref()
+            .or(self.auto_title.as_deref())
+            .or(self.preview.as_deref())
+    }
+
     pub fn resume_hint(&self) -> String {
         format!("codex resume {}", self.id.cyan())
     }
+
+    /// Short `owner/name` of the recorded repository.
+    pub fn repo_label(&self) -> Option<String> {
+        self.git_repository.as_deref().map(repo_label)
+    }
+
+    /// The session was started but never received a user message, e.g. an aborted launch.
+    pub fn is_empty(&self) -> bool {
+        self.turns == 0
+    }
+
+    /// Time between the first and last record of the rollout.
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        Some(self.updated_at? - self.created_at?)
+    }
+}
+
+/// Cumulative token usage reported by the last `token_count` event of a rollout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTotals {
+    pub input: u64,
+    pub cached_input: u64,
+    pub output: u64,
+    pub reasoning: u64,
+    pub total: u64,
+}
+
+impl From<&TokenUsage> for TokenTotals {
+    fn from(usage: &TokenUsage) -> Self {
+        let clamp = |value: i64| u64::try_from(value).unwrap_or(0);
+        Self {
+            input: clamp(usage.input_tokens),
+            cached_input: clamp(usage.cached_input_tokens),
+            output: clamp(usage.output_tokens),
+            reasoning: clamp(usage.reasoning_output_tokens),
+            total: clamp(usage.total_tokens),
+        }
+    }
+}
+
+/// The `source` recorded in a rollout's `session_meta`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    Cli,
+    Vscode,
+    Exec,
+    Mcp,
+    #[default]
+    Unknown,
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 5] = [
+        SourceKind::Cli,
+        SourceKind::Vscode,
+        SourceKind::Exec,
+        SourceKind::Mcp,
+        SourceKind::Unknown,
+    ];
+
+    /// Sessions a person worked in, offered by `resume` and the TUI unless asked otherwise.
+    pub const INTERACTIVE: [SourceKind; 2] = [SourceKind::Cli, SourceKind::Vscode];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceKind::Cli => "cli",
+            SourceKind::Vscode => "vscode",
+            SourceKind::Exec => "exec",
+            SourceKind::Mcp => "mcp",
+            SourceKind::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&SessionSource> for SourceKind {
+    fn from(source: &SessionSource) -> Self {
+        // Go through the wire name so sources added to Codex later show up as `unknown`.
+        serde_json::to_value(source)
+            .ok()
+            .and_then(|value| value.as_str().and_then(|name| name.parse().ok()))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized = value.trim().to_ascii_lowercase();
+        if normalized == "vs_code" || normalized == "vs-code" {
+            return Ok(SourceKind::Vscode);
+        }
+        SourceKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "unknown session source `{value}` (expected one of: {})",
+                    SourceKind::ALL.map(SourceKind::as_str).join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Updated,
+    Created,
+    Cwd,
+    Branch,
+    Size,
+    Turns,
+    Tokens,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 7] = [
+        SortKey::Updated,
+        SortKey::Created,
+        SortKey::Cwd,
+        SortKey::Branch,
+        SortKey::Size,
+        SortKey::Turns,
+        SortKey::Tokens,
+    ];
+
+    /// Whether the scan already yields sessions roughly in this order, so sorting a page
+    /// of them is enough; other keys need every matching session sorted first.
+    pub fn is_chronological(self) -> bool {
+        matches!(self, SortKey::Updated | SortKey::Created)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortKey::Updated => "updated",
+            SortKey::Created => "created",
+            SortKey::Cwd => "cwd",
+            SortKey::Branch => "branch",
+            SortKey::Size => "size",
+            SortKey::Turns => "turns",
+            SortKey::Tokens => "tokens",
+        }
+    }
+
+    /// The next key in display order, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = SortKey::ALL
+            .iter()
+            .position(|key| *key == self)
+            .unwrap_or(0);
+        SortKey::ALL[(idx + 1) % SortKey::ALL.len()]
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SortKey::ALL
+            .into_iter()
+            .find(|key| key.as_str().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown sort key `{value}` (expected one of: {})",
+                    SortKey::ALL.map(SortKey::as_str).join(", ")
+                )
+            })
+    }
+}
+
+/// Stable sort with pinned sessions first, so ties keep their newest-first order.
+///
+/// Times and amounts sort largest first; directories and branches sort alphabetically.
+pub fn sort_sessions(sessions: &mut [SessionSummary], key: SortKey) {
+    sessions.sort_by(|a, b| {
+        let order = match key {
+            SortKey::Updated => b.updated_at.cmp(&a.updated_at),
+            SortKey::Created => b.created_at.cmp(&a.created_at),
+            SortKey::Cwd => a.cwd.cmp(&b.cwd),
+            SortKey::Branch => a.git_branch.cmp(&b.git_branch),
+            SortKey::Size => b.size.cmp(&a.size),
+            SortKey::Turns => b.turns.cmp(&a.turns),
+            SortKey::Tokens => {
+                let total = |summary: &SessionSummary| summary.tokens.map(|t| t.total).unwrap_or(0);
+                total(b).cmp(&total(a))
+            }
+        };
+        b.pinned.cmp(&a.pinned).then(order)
+    });
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct SessionList {
+    /// Pinned sessions matching the filters, independent of the cursor and limit.
+    pub pinned: Vec<SessionSummary>,
     pub sessions: Vec<SessionSummary>,
     pub next_cursor: Option<String>,
     pub scanned_files: usize,
     pub reached_scan_cap: bool,
 }
 
+impl SessionList {
+    /// Pinned sessions followed by the current page.
+    pub fn into_all(self) -> Vec<SessionSummary> {
+        let mut all = self.pinned;
+        all.extend(self.sessions);
+        all
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SessionDetail {
     pub summary: SessionSummary,
@@ -60,6 +325,167 @@ pub struct SessionDetail {
     pub source: Option<SessionSource>,
     pub git_branch: Option<String>,
     pub meta: Option<SessionMetaLine>,
+    pub note: Option<String>,
+    /// Settings of the last turn; `None` for rollouts without `turn_context` records.
+    pub turn_context: Option<TurnSettings>,
+}
+
+/// Model and permission settings Codex recorded for a turn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TurnSettings {
+    pub model: String,
+    /// Reasoning effort, e.g. `high`.
+    pub effort: Option<String>,
+    /// `untrusted`, `on-failure`, `on-request` or `never`.
+    pub approval_policy: Option<String>,
+    /// `read-only`, `workspace-write` or `danger-full-access`.
+    pub sandbox: Option<String>,
+    /// Whether a `workspace-write` sandbox allowed network access.
+    pub network_access: bool,
+    /// Directories a `workspace-write` sandbox could write to besides the CWD.
+    pub writable_roots: Vec<PathBuf>,
+}
+
+impl From<&TurnContextItem> for TurnSettings {
+    fn from(context: &TurnContextItem) -> Self {
+        // Go through the wire names, which are also the values Codex's flags accept.
+        let value = serde_json::to_value(context).unwrap_or_default();
+        let text = |value: &Value| value.as_str().map(str::to_string);
+        let sandbox = &value["sandbox_policy"];
+        Self {
+            model: context.model.clone(),
+            effort: text(&value["effort"]),
+            approval_policy: text(&value["approval_policy"]),
+            sandbox: text(&sandbox["mode"]),
+            network_access: sandbox["network_access"].as_bool().unwrap_or(false),
+            writable_roots: serde_json::from_value(sandbox["writable_roots"].clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TurnSettings {
+    /// `gpt-5-codex (high)`, or just the model when no effort was recorded.
+    pub fn model_label(&self) -> String {
+        match self.effort.as_deref() {
+            Some(effort) => format!("{} ({effort})", self.model),
+            None => self.model.clone(),
+        }
+    }
+
+    /// `workspace-write, network, +2 writable roots`.
+    pub fn sandbox_label(&self) -> String {
+        let mut label = self.sandbox.as_deref().unwrap_or("unknown").to_string();
+        if self.network_access {
+            label.push_str(", network");
+        }
+        match self.writable_roots.len() {
+            0 => {}
+            1 => label.push_str(", +1 writable root"),
+            count => label.push_str(&format!(", +{count} writable roots")),
+        }
+        label
+    }
+}
+
+/// Which project directories a listing covers, judged by each session's recorded CWD.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CwdScope {
+    #[default]
+    All,
+    /// Sessions recorded in exactly one of these directories, e.g. one per worktree.
+    Exact(Vec<PathBuf>),
+    /// Sessions recorded in any of these directories or below them.
+    Within(Vec<PathBuf>),
+    /// Sessions recorded in a directory matching the pattern, or below one when `recursive`.
+    Glob {
+        pattern: glob::Pattern,
+        recursive: bool,
+    },
+}
+
+impl CwdScope {
+    pub fn matches(&self, cwd: Option<&Path>) -> bool {
+        let Some(cwd) = cwd else {
+            return matches!(self, CwdScope::All);
+        };
+        match self {
+            CwdScope::All => true,
+            CwdScope::Exact(dirs) => {
+                let cwd = canonical(cwd);
+                dirs.iter().any(|dir| cwd == canonical(dir))
+            }
+            CwdScope::Within(roots) => {
+                let cwd = canonical(cwd);
+                roots.iter().any(|root| cwd.starts_with(canonical(root)))
+            }
+            CwdScope::Glob { pattern, recursive } => {
+                let options = glob::MatchOptions {
+                    require_literal_separator: true,
+                    ..glob::MatchOptions::new()
+                };
+                // Match the recorded path as well, so moved or deleted directories still count.
+                [cwd.to_path_buf(), canonical(cwd)].iter().any(|path| {
+                    if *recursive {
+                        path.ancestors()
+                            .any(|dir| pattern.matches_path_with(dir, options))
+                    } else {
+                        pattern.matches_path_with(path, options)
+                    }
+                })
+            }
+        }
+    }
+
+    /// Short description for headers, e.g. `under ~/code/app (+1 worktrees)`.
+    pub fn describe(&self) -> String {
+        let dirs = |kind: &str, dirs: &[PathBuf]| match dirs {
+            [] => String::from("no directories"),
+            [dir] => format!("{kind} {}", crate::shorten_path(dir, 40)),
+            [dir, rest @ ..] => format!(
+                "{kind} {} (+{} worktrees)",
+                crate::shorten_path(dir, 40),
+                rest.len()
+            ),
+        };
+        match self {
+            CwdScope::All => String::from("all projects"),
+            CwdScope::Exact(list) => dirs("cwd", list),
+            CwdScope::Within(list) => dirs("under", list),
+            CwdScope::Glob { pattern, .. } => format!("cwd {pattern}"),
+        }
+    }
+}
+
+/// Old project directories and where they live now, for sessions recorded before a move.
+#[derive(Debug, Clone, Default)]
+pub struct PathRemap(Vec<(PathBuf, PathBuf)>);
+
+impl PathRemap {
+    pub fn new(pairs: impl IntoIterator<Item = (PathBuf, PathBuf)>) -> Self {
+        let mut pairs: Vec<_> = pairs.into_iter().collect();
+        // Most specific first, so a nested entry wins over its parent.
+        pairs.sort_by_key(|(from, _)| Reverse(from.components().count()));
+        Self(pairs)
+    }
+
+    /// Where `path` lives now, if it is inside a remapped directory.
+    pub fn apply(&self, path: &Path) -> Option<PathBuf> {
+        self.0.iter().find_map(|(from, to)| {
+            let rest = path.strip_prefix(from).ok()?;
+            Some(if rest.as_os_str().is_empty() {
+                to.clone()
+            } else {
+                to.join(rest)
+            })
+        })
+    }
+
+    fn apply_to(&self, summary: &mut SessionSummary) {
+        if let Some(cwd) = summary.cwd.as_deref().and_then(|cwd| self.apply(cwd)) {
+            summary.cwd = Some(cwd);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -67,8 +493,23 @@ pub struct ListOptions {
     pub limit: usize,
     pub cursor: Option<String>,
     pub providers: Vec<String>,
-    pub show_all: bool,
-    pub cwd_filter: Option<PathBuf>,
+    pub scope: CwdScope,
+    /// Rewrites recorded CWDs of moved projects before filtering and display.
+    pub remap: PathRemap,
+    /// List the archive directory instead of live sessions.
+    pub archived: bool,
+    /// Only keep sessions carrying every one of these tags.
+    pub tags: Vec<String>,
+    /// Only keep sessions started by one of these sources; empty keeps all of them.
+    pub sources: Vec<SourceKind>,
+    /// Also list sessions without any user message.
+    pub include_empty: bool,
+    /// Only keep sessions whose repository URL contains this text (case-insensitive).
+    pub repo: Option<String>,
+    /// Only keep sessions recorded on this branch.
+    pub branch: Option<String>,
+    /// Only keep sessions whose recorded commit starts with this hash prefix.
+    pub commit: Option<String>,
 }
 
 impl Default for ListOptions {
@@ -77,8 +518,15 @@ impl Default for ListOptions {
             limit: 20,
             cursor: None,
             providers: Vec::new(),
-            show_all: false,
-            cwd_filter: None,
+            scope: CwdScope::All,
+            remap: PathRemap::default(),
+            archived: false,
+            tags: Vec::new(),
+            sources: Vec::new(),
+            include_empty: false,
+            repo: None,
+            branch: None,
+            commit: None,
         }
     }
 }
@@ -92,9 +540,14 @@ pub enum SessionError {
 }
 
 pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionList> {
-    let root = codex_home.join(SESSIONS_SUBDIR);
+    let root = codex_home.join(if opts.archived {
+        ARCHIVED_SUBDIR
+    } else {
+        SESSIONS_SUBDIR
+    });
     if !root.exists() {
         return Ok(SessionList {
+            pinned: Vec::new(),
             sessions: Vec::new(),
             next_cursor: None,
             scanned_files: 0,
@@ -102,12 +555,14 @@ pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionLis
         });
     }
 
-    let anchor = opts.cursor.as_deref().and_then(|token| parse_cursor(token));
+    let anchor = opts.cursor.as_deref().and_then(parse_cursor);
     let (mut anchor_passed, anchor_ts, anchor_id) = match anchor {
         Some(cursor) => (false, cursor.ts, cursor.id),
         None => (true, OffsetDateTime::UNIX_EPOCH, Uuid::nil()),
     };
 
+    let mut index = SessionIndex::load(codex_home);
+    let annotations = AnnotationStore::load_or_empty(codex_home);
     let mut collected: Vec<SessionSummary> = Vec::new();
     let mut scanned_files = 0usize;
     let mut reached_scan_cap = false;
@@ -138,29 +593,13 @@ pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionLis
                         }
                     }
 
-                    match summarize_session(&path)? {
-                        Some(summary) => {
-                            if !opts.show_all {
-                                if let Some(filter) = opts.cwd_filter.as_ref() {
-                                    if let Some(row_cwd) = summary.cwd.as_ref() {
-                                        if !paths_match(row_cwd, filter) {
-                                            continue;
-                                        }
-                                    } else {
-                                        continue;
-                                    }
-                                }
-                            }
-
-                            if !opts.providers.is_empty() {
-                                let provider = summary.provider.as_deref().unwrap_or("");
-                                if !opts
-                                    .providers
-                                    .iter()
-                                    .any(|candidate| candidate.eq_ignore_ascii_case(provider))
-                                {
-                                    continue;
-                                }
+                    match index.summarize(&path)? {
+                        Some(mut summary) => {
+                            annotations.apply(&mut summary);
+                            opts.remap.apply_to(&mut summary);
+                            // Pinned sessions are listed separately, on every page.
+                            if summary.pinned || !matches_filters(&summary, opts) {
+                                continue;
                             }
 
                             collected.push(summary);
@@ -176,6 +615,11 @@ pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionLis
         }
     }
 
+    let pinned = collect_pinned(&root, &mut index, &annotations, opts)?;
+
+    // The index is only a cache; a read-only Codex home must not break listing.
+    let _ = index.save();
+
     let next_cursor = if more_matches_available {
         collected
             .last()
@@ -185,6 +629,7 @@ pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionLis
     };
 
     Ok(SessionList {
+        pinned,
         sessions: collected,
         next_cursor,
         scanned_files,
@@ -192,21 +637,136 @@ pub fn list_sessions(codex_home: &Path, opts: &ListOptions) -> Result<SessionLis
     })
 }
 
-pub fn load_session_detail(_codex_home: &Path, path: &Path) -> Result<SessionDetail> {
-    let summary = summarize_session(path)?.ok_or(SessionError::NotFound)?;
+fn matches_filters(summary: &SessionSummary, opts: &ListOptions) -> bool {
+    if summary.is_empty() && !opts.include_empty {
+        return false;
+    }
+
+    if !opts.scope.matches(summary.cwd.as_deref()) {
+        return false;
+    }
+
+    if !opts.sources.is_empty() && !opts.sources.contains(&summary.source) {
+        return false;
+    }
+
+    if let Some(repo) = opts.repo.as_deref()
+        && !summary.git_repository.as_deref().is_some_and(|url| {
+            url.to_ascii_lowercase()
+                .contains(&repo.to_ascii_lowercase())
+        })
+    {
+        return false;
+    }
+
+    if let Some(branch) = opts.branch.as_deref()
+        && summary.git_branch.as_deref() != Some(branch)
+    {
+        return false;
+    }
+
+    if let Some(commit) = opts.commit.as_deref()
+        && !summary.git_commit.as_deref().is_some_and(|hash| {
+            hash.to_ascii_lowercase()
+                .starts_with(&commit.to_ascii_lowercase())
+        })
+    {
+        return false;
+    }
+
+    if !opts.providers.is_empty() {
+        let provider = summary.provider.as_deref().unwrap_or("");
+        if !opts
+            .providers
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(provider))
+        {
+            return false;
+        }
+    }
+
+    opts.tags.iter().all(|wanted| {
+        summary
+            .tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case(wanted))
+    })
+}
+
+/// Find every pinned rollout under `root` that passes the list filters, newest first.
+fn collect_pinned(
+    root: &Path,
+    index: &mut SessionIndex,
+    annotations: &AnnotationStore,
+    opts: &ListOptions,
+) -> Result<Vec<SessionSummary>> {
+    let wanted = annotations.pinned_ids();
+    let mut pinned = Vec::new();
+    if wanted.is_empty() {
+        return Ok(pinned);
+    }
+    for entry in WalkDir::new(root).into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let is_pinned = entry
+            .file_name()
+            .to_str()
+            .and_then(parse_timestamp_uuid_from_filename)
+            .is_some_and(|(_, uuid)| wanted.contains(&uuid.to_string()));
+        if !is_pinned {
+            continue;
+        }
+        if let Some(mut summary) = index.summarize(entry.path())? {
+            annotations.apply(&mut summary);
+            opts.remap.apply_to(&mut summary);
+            if matches_filters(&summary, opts) {
+                pinned.push(summary);
+            }
+        }
+    }
+    pinned.sort_by_key(|summary| Reverse(summary.updated_at));
+    Ok(pinned)
+}
+
+pub fn load_session_detail(codex_home: &Path, path: &Path) -> Result<SessionDetail> {
+    let mut summary = summarize_session(path)?.ok_or(SessionError::NotFound)?;
     let head = read_head_summary(path, HEAD_RECORD_LIMIT)?;
     let meta = extract_session_meta(&head.head);
+    let annotations = AnnotationStore::load_or_empty(codex_home);
+    annotations.apply(&mut summary);
     Ok(SessionDetail {
+        note: annotations
+            .get(&summary.id)
+            .and_then(|entry| entry.note.clone()),
         git_branch: summary.git_branch.clone(),
         instructions: meta
             .as_ref()
             .and_then(|line| line.meta.instructions.clone()),
         source: meta.as_ref().map(|line| line.meta.source.clone()),
+        turn_context: read_turn_settings(path)?,
         summary,
         meta,
     })
 }
 
+/// Settings of the last turn recorded in a rollout.
+pub fn read_turn_settings(path: &Path) -> Result<Option<TurnSettings>> {
+    let file = File::open(path).with_context(|| format!("failed to open session file {path:?}"))?;
+    let mut last = None;
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if !line.contains("\"turn_context\"") {
+            continue;
+        }
+        if let Ok(parsed) = serde_json::from_str::<RolloutLine>(line.trim())
+            && let RolloutItem::TurnContext(context) = parsed.item
+        {
+            last = Some(context);
+        }
+    }
+    Ok(last.as_ref().map(TurnSettings::from))
+}
+
 pub fn resolve_session_path(codex_home: &Path, query: &str) -> Result<PathBuf> {
     let path = PathBuf::from(query);
     if path.exists() {
@@ -215,20 +775,21 @@ pub fn resolve_session_path(codex_home: &Path, query: &str) -> Result<PathBuf> {
 
     let uuid = Uuid::parse_str(query)
         .with_context(|| format!("{query} is not a valid UUID or file path"))?;
-    let sessions_root = codex_home.join(SESSIONS_SUBDIR);
-    if !sessions_root.exists() {
-        return Err(SessionError::NotFound.into());
-    }
-
-    for entry in WalkDir::new(&sessions_root).into_iter().flatten() {
-        if !entry.file_type().is_file() {
+    // Live sessions take precedence; the archive is searched so archived ids still resolve.
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SUBDIR] {
+        let root = codex_home.join(subdir);
+        if !root.exists() {
             continue;
         }
-        if let Some(file_name) = entry.file_name().to_str() {
-            if let Some((_, file_uuid)) = parse_timestamp_uuid_from_filename(file_name) {
-                if file_uuid == uuid {
-                    return Ok(entry.into_path());
-                }
+        for entry in WalkDir::new(&root).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            if let Some(file_name) = entry.file_name().to_str()
+                && let Some((_, file_uuid)) = parse_timestamp_uuid_from_filename(file_name)
+                && file_uuid == uuid
+            {
+                return Ok(entry.into_path());
             }
         }
     }
@@ -236,7 +797,7 @@ pub fn resolve_session_path(codex_home: &Path, query: &str) -> Result<PathBuf> {
     Err(SessionError::NotFound.into())
 }
 
-pub fn export_session_chat(source: &Path, target: &Path) -> Result<()> {
+pub fn export_session_chat(source: &Path, target: &Path, opts: &ExportOptions) -> Result<()> {
     let is_jsonl = target
         .extension()
         .map(|ext| ext.eq_ignore_ascii_case("jsonl"))
@@ -262,7 +823,7 @@ pub fn export_session_chat(source: &Path, target: &Path) -> Result<()> {
         return Ok(());
     }
 
-    let (meta_line, entries) = read_session_entries(source)?;
+    let (meta_line, entries) = read_session_entries(source, opts)?;
 
     if is_json {
         let writer = BufWriter::new(
@@ -274,12 +835,12 @@ pub fn export_session_chat(source: &Path, target: &Path) -> Result<()> {
     }
 
     if is_pdf {
-        let markdown = render_markdown(meta_line.as_ref(), &entries);
+        let markdown = render_markdown(meta_line.as_ref(), opts.title.as_deref(), &entries, false);
         export_markdown_pdf(&markdown, target)?;
         return Ok(());
     }
 
-    let markdown = render_markdown(meta_line.as_ref(), &entries);
+    let markdown = render_markdown(meta_line.as_ref(), opts.title.as_deref(), &entries, true);
     let mut writer = BufWriter::new(
         File::create(target).with_context(|| format!("failed to create export file {target:?}"))?,
     );
@@ -288,13 +849,265 @@ pub fn export_session_chat(source: &Path, target: &Path) -> Result<()> {
     Ok(())
 }
 
-#[derive(Serialize)]
-struct ChatEntry {
-    role: String,
-    content: String,
+/// Categories of rollout items that can appear in an exported transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Message,
+    Reasoning,
+    ToolCall,
+    ToolOutput,
+    WebSearch,
+}
+
+impl EntryKind {
+    pub const ALL: [EntryKind; 5] = [
+        EntryKind::Message,
+        EntryKind::Reasoning,
+        EntryKind::ToolCall,
+        EntryKind::ToolOutput,
+        EntryKind::WebSearch,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryKind::Message => "message",
+            EntryKind::Reasoning => "reasoning",
+            EntryKind::ToolCall => "tool_call",
+            EntryKind::ToolOutput => "tool_output",
+            EntryKind::WebSearch => "web_search",
+        }
+    }
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntryKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized = value.trim().to_ascii_lowercase().replace('-', "_");
+        match normalized.as_str() {
+            "message" | "messages" => Ok(EntryKind::Message),
+            "reasoning" => Ok(EntryKind::Reasoning),
+            "tool_call" | "tool_calls" | "call" | "calls" => Ok(EntryKind::ToolCall),
+            "tool_output" | "tool_outputs" | "output" | "outputs" => Ok(EntryKind::ToolOutput),
+            "web_search" | "search" => Ok(EntryKind::WebSearch),
+            _ => Err(format!(
+                "unknown item kind `{value}` (expected one of: {})",
+                EntryKind::ALL.map(EntryKind::as_str).join(", ")
+            )),
+        }
+    }
+}
+
+/// Controls which rollout items are exported and how much tool output is kept.
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub kinds: Vec<EntryKind>,
+    /// Tool output longer than this many lines is truncated; `None` keeps everything.
+    pub max_output_lines: Option<usize>,
+    /// Heading for Markdown and PDF exports; the session id is used when unset.
+    pub title: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            kinds: EntryKind::ALL.to_vec(),
+            max_output_lines: Some(DEFAULT_MAX_OUTPUT_LINES),
+            title: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatEntry {
+    Message {
+        role: String,
+        content: String,
+    },
+    Reasoning {
+        content: String,
+    },
+    ToolCall {
+        name: String,
+        call_id: Option<String>,
+        input: String,
+    },
+    ToolOutput {
+        call_id: Option<String>,
+        output: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        omitted_lines: Option<usize>,
+    },
+    WebSearch {
+        query: String,
+    },
+}
+
+impl ChatEntry {
+    pub fn kind(&self) -> EntryKind {
+        match self {
+            ChatEntry::Message { .. } => EntryKind::Message,
+            ChatEntry::Reasoning { .. } => EntryKind::Reasoning,
+            ChatEntry::ToolCall { .. } => EntryKind::ToolCall,
+            ChatEntry::ToolOutput { .. } => EntryKind::ToolOutput,
+            ChatEntry::WebSearch { .. } => EntryKind::WebSearch,
+        }
+    }
+
+    /// Short human-readable heading, e.g. `assistant` or `tool call shell`.
+    pub fn label(&self) -> String {
+        match self {
+            ChatEntry::Message { role, .. } => role.clone(),
+            ChatEntry::Reasoning { .. } => String::from("reasoning"),
+            ChatEntry::ToolCall { name, .. } => format!("tool call {name}"),
+            ChatEntry::ToolOutput { .. } => String::from("tool output"),
+            ChatEntry::WebSearch { .. } => String::from("web search"),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            ChatEntry::Message { content, .. } | ChatEntry::Reasoning { content } => content,
+            ChatEntry::ToolCall { input, .. } => input,
+            ChatEntry::ToolOutput { output, .. } => output,
+            ChatEntry::WebSearch { query } => query,
+        }
+    }
+
+    fn truncate_output(&mut self, max_lines: usize) {
+        if let ChatEntry::ToolOutput {
+            output,
+            omitted_lines,
+            ..
+        } = self
+        {
+            let total = output.lines().count();
+            if total > max_lines {
+                *output = output
+                    .lines()
+                    .take(max_lines)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                *omitted_lines = Some(total - max_lines);
+            }
+        }
+    }
+}
+
+/// Convert a response item into a transcript entry, skipping items with no readable text.
+pub(crate) fn chat_entry_from_item(item: ResponseItem) -> Option<ChatEntry> {
+    let entry = match item {
+        ResponseItem::Message { role, content, .. } => ChatEntry::Message {
+            role,
+            content: flatten_content(&content),
+        },
+        ResponseItem::Reasoning {
+            summary, content, ..
+        } => {
+            let mut pieces: Vec<String> = summary
+                .into_iter()
+                .map(|ReasoningItemReasoningSummary::SummaryText { text }| text)
+                .collect();
+            if pieces.is_empty() {
+                pieces = content
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|part| match part {
+                        ReasoningItemContent::ReasoningText { text }
+                        | ReasoningItemContent::Text { text } => text,
+                    })
+                    .collect();
+            }
+            ChatEntry::Reasoning {
+                content: pieces.join("\n\n"),
+            }
+        }
+        ResponseItem::LocalShellCall {
+            call_id,
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => ChatEntry::ToolCall {
+            name: String::from("local_shell"),
+            call_id,
+            input: exec.command.join(" "),
+        },
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => ChatEntry::ToolCall {
+            name,
+            call_id: Some(call_id),
+            input: readable_arguments(arguments),
+        },
+        ResponseItem::CustomToolCall {
+            name,
+            input,
+            call_id,
+            ..
+        } => ChatEntry::ToolCall {
+            name,
+            call_id: Some(call_id),
+            input,
+        },
+        ResponseItem::FunctionCallOutput { call_id, output } => ChatEntry::ToolOutput {
+            call_id: Some(call_id),
+            output: output.content,
+            omitted_lines: None,
+        },
+        ResponseItem::CustomToolCallOutput { call_id, output } => ChatEntry::ToolOutput {
+            call_id: Some(call_id),
+            output,
+            omitted_lines: None,
+        },
+        ResponseItem::WebSearchCall { action, .. } => ChatEntry::WebSearch {
+            query: match action {
+                WebSearchAction::Search { query } => query.unwrap_or_default(),
+                WebSearchAction::OpenPage { url } => {
+                    format!("open {}", url.unwrap_or_default())
+                }
+                WebSearchAction::FindInPage { url, pattern } => format!(
+                    "find \"{}\" in {}",
+                    pattern.unwrap_or_default(),
+                    url.unwrap_or_default()
+                ),
+                WebSearchAction::Other => String::new(),
+            },
+        },
+        _ => return None,
+    };
+    if entry.text().trim().is_empty() {
+        None
+    } else {
+        Some(entry)
+    }
+}
+
+/// Shell calls carry their argv as JSON; show it as a command line instead.
+fn readable_arguments(arguments: String) -> String {
+    let command = serde_json::from_str::<Value>(&arguments)
+        .ok()
+        .and_then(|value| value.get("command").cloned())
+        .and_then(|command| serde_json::from_value::<Vec<String>>(command).ok());
+    match command {
+        Some(argv) => argv.join(" "),
+        None => arguments,
+    }
 }
 
-fn read_session_entries(source: &Path) -> Result<(Option<SessionMetaLine>, Vec<ChatEntry>)> {
+pub(crate) fn read_session_entries(
+    source: &Path,
+    opts: &ExportOptions,
+) -> Result<(Option<SessionMetaLine>, Vec<ChatEntry>)> {
     let file =
         File::open(source).with_context(|| format!("failed to open session file {source:?}"))?;
     let reader = BufReader::new(file);
@@ -310,21 +1123,20 @@ fn read_session_entries(source: &Path) -> Result<(Option<SessionMetaLine>, Vec<C
             continue;
         };
         match rollout_line.item {
-            RolloutItem::SessionMeta(meta) => {
-                if meta_line.is_none() {
-                    meta_line = Some(meta);
-                }
+            RolloutItem::SessionMeta(meta) if meta_line.is_none() => {
+                meta_line = Some(meta);
             }
             RolloutItem::ResponseItem(item) => {
-                if let ResponseItem::Message { role, content, .. } = item {
-                    let text = flatten_content(&content);
-                    if !text.trim().is_empty() {
-                        entries.push(ChatEntry {
-                            role,
-                            content: text,
-                        });
-                    }
+                let Some(mut entry) = chat_entry_from_item(item) else {
+                    continue;
+                };
+                if !opts.kinds.contains(&entry.kind()) {
+                    continue;
+                }
+                if let Some(max_lines) = opts.max_output_lines {
+                    entry.truncate_output(max_lines);
                 }
+                entries.push(entry);
             }
             _ => {}
         }
@@ -332,33 +1144,84 @@ fn read_session_entries(source: &Path) -> Result<(Option<SessionMetaLine>, Vec<C
     Ok((meta_line, entries))
 }
 
-fn render_markdown(meta_line: Option<&SessionMetaLine>, entries: &[ChatEntry]) -> String {
+fn render_markdown(
+    meta_line: Option<&SessionMetaLine>,
+    title: Option<&str>,
+    entries: &[ChatEntry],
+    collapsible: bool,
+) -> String {
     let mut buf = String::new();
+    if let Some(title) = title {
+        buf.push_str(&format!("# {title}\n\n"));
+    }
     if let Some(meta) = meta_line {
-        buf.push_str(&format!("# Session {}\n\n", meta.meta.id));
+        if title.is_some() {
+            buf.push_str(&format!("- session: {}\n", meta.meta.id));
+        } else {
+            buf.push_str(&format!("# Session {}\n\n", meta.meta.id));
+        }
         buf.push_str(&format!("- started: {}\n", meta.meta.timestamp));
         buf.push_str(&format!("- cwd: {}\n", meta.meta.cwd.display()));
         if let Some(provider) = meta.meta.model_provider.as_deref() {
             buf.push_str(&format!("- provider: {}\n", provider));
         }
-        buf.push_str("\n");
+        buf.push('\n');
     }
     for entry in entries {
-        if entry.content.trim().is_empty() {
-            continue;
+        match entry {
+            ChatEntry::Message { role, content } => {
+                buf.push_str(&format!(
+                    "**{}**\n{}\n\n",
+                    role.to_uppercase(),
+                    content.trim()
+                ));
+            }
+            ChatEntry::Reasoning { content } => {
+                buf.push_str("**REASONING**\n");
+                for line in content.trim().lines() {
+                    buf.push_str(&format!("> {line}\n"));
+                }
+                buf.push('\n');
+            }
+            ChatEntry::ToolCall { name, input, .. } => {
+                buf.push_str(&format!(
+                    "**TOOL CALL** `{name}`\n```\n{}\n```\n\n",
+                    input.trim()
+                ));
+            }
+            ChatEntry::ToolOutput {
+                output,
+                omitted_lines,
+                ..
+            } => {
+                let omitted = omitted_lines
+                    .map(|count| format!("\n… {count} more lines omitted"))
+                    .unwrap_or_default();
+                if collapsible {
+                    buf.push_str(&format!(
+                        "<details>\n<summary>Tool output</summary>\n\n```\n{}{omitted}\n```\n\n</details>\n\n",
+                        output.trim_end()
+                    ));
+                } else {
+                    buf.push_str(&format!(
+                        "**TOOL OUTPUT**\n```\n{}{omitted}\n```\n\n",
+                        output.trim_end()
+                    ));
+                }
+            }
+            ChatEntry::WebSearch { query } => {
+                buf.push_str(&format!("**WEB SEARCH** {}\n\n", query.trim()));
+            }
         }
-        buf.push_str(&format!(
-            "**{}**\n{}\n\n",
-            entry.role.to_uppercase(),
-            entry.content.trim()
-        ));
     }
     buf
 }
 
-fn summarize_session(path: &Path) -> Result<Option<SessionSummary>> {
+pub(crate) fn summarize_session(path: &Path) -> Result<Option<SessionSummary>> {
     let summary = read_head_summary(path, HEAD_RECORD_LIMIT)?;
-    if !summary.saw_session_meta || !summary.saw_user_event {
+    // Sessions without a user message are still summarized so they can be inspected and
+    // pruned; listings hide them unless asked (see `SessionSummary::is_empty`).
+    if !summary.saw_session_meta {
         return Ok(None);
     }
 
@@ -368,18 +1231,13 @@ fn summarize_session(path: &Path) -> Result<Option<SessionSummary>> {
     };
     let SessionMetaLine { meta, git } = meta_line;
 
-    if !INTERACTIVE_SOURCES
-        .iter()
-        .any(|source| source == &meta.source)
-    {
-        return Ok(None);
-    }
-
     let preview = preview_from_head(&summary.head);
+    let scan = scan_rollout(path)?;
     let created_at = summary.created_at.as_deref().and_then(parse_timestamp_str);
-    let updated_at = summary
-        .updated_at
+    let updated_at = scan
+        .last_timestamp
         .as_deref()
+        .or(summary.updated_at.as_deref())
         .and_then(parse_timestamp_str)
         .or_else(|| file_modified_time(path).ok().flatten())
         .or(created_at);
@@ -391,17 +1249,97 @@ fn summarize_session(path: &Path) -> Result<Option<SessionSummary>> {
         created_at,
         updated_at,
         cwd: Some(meta.cwd.clone()),
-        git_branch: git.and_then(|info| info.branch),
+        git_branch: git.as_ref().and_then(|info| info.branch.clone()),
+        git_commit: git.as_ref().and_then(|info| info.commit_hash.clone()),
+        git_repository: git.and_then(|info| info.repository_url),
+        git_status: None,
         provider: meta.model_provider.clone(),
+        model: scan.model,
+        source: SourceKind::from(&meta.source),
+        tokens: scan.tokens,
+        turns: scan.turns,
+        size: fs::metadata(path).map(|meta| meta.len()).unwrap_or(0),
+        tags: Vec::new(),
+        pinned: false,
+        title: None,
+        auto_title: scan.auto_title,
     }))
 }
 
+#[derive(Default)]
+struct RolloutScan {
+    tokens: Option<TokenTotals>,
+    model: Option<String>,
+    turns: usize,
+    last_timestamp: Option<String>,
+    auto_title: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TimestampOnly {
+    timestamp: String,
+}
+
+/// Walk the whole rollout for token totals, model, turn count, last activity and title hints.
+fn scan_rollout(path: &Path) -> io::Result<RolloutScan> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut scan = RolloutScan::default();
+    let mut hints = TitleHints::default();
+    let mut last_line = String::new();
+    for line in reader.lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Only a few record types matter, so avoid parsing everything else.
+        let relevant = line.contains("\"token_count\"")
+            || line.contains("\"turn_context\"")
+            || line.contains("\"user_message\"")
+            || line.contains("\"function_call\"")
+            || line.contains("\"custom_tool_call\"")
+            || line.contains("\"local_shell_call\"");
+        if relevant && let Ok(parsed) = serde_json::from_str::<RolloutLine>(line.trim()) {
+            match parsed.item {
+                RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                    if let Some(info) = event.info {
+                        scan.tokens = Some(TokenTotals::from(&info.total_token_usage));
+                    }
+                }
+                RolloutItem::TurnContext(context) => {
+                    scan.model = Some(context.model);
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                    scan.turns += 1;
+                    if !is_session_prefix(&event.message)
+                        && !looks_like_instructions(&event.message)
+                    {
+                        hints.observe_user_message(&event.message);
+                    }
+                }
+                RolloutItem::ResponseItem(item) => {
+                    if let Some(ChatEntry::ToolCall { name, input, .. }) =
+                        chat_entry_from_item(item)
+                    {
+                        hints.observe_tool_call(&name, &input);
+                    }
+                }
+                _ => {}
+            }
+        }
+        last_line = line;
+    }
+    scan.last_timestamp = serde_json::from_str::<TimestampOnly>(last_line.trim())
+        .ok()
+        .map(|line| line.timestamp);
+    scan.auto_title = hints.title();
+    Ok(scan)
+}
+
 fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadSummary> {
     let file = File::open(path)?;
     let reader = BufReader::new(file);
     let mut summary = HeadSummary::default();
 
-    for line in reader.lines().flatten() {
+    for line in reader.lines().map_while(Result::ok) {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
@@ -477,21 +1415,18 @@ fn preview_from_response_item(item: ResponseItem) -> Option<String> {
         ResponseItem::Message { role, content, .. } if role == "user" => {
             let mut pieces: Vec<String> = Vec::new();
             for entry in content {
-                match entry {
-                    ContentItem::InputText { text } => {
-                        if is_session_prefix(&text) {
-                            return None;
-                        }
-                        let trimmed = text.trim();
-                        if trimmed.is_empty() {
-                            continue;
-                        }
-                        if looks_like_instructions(trimmed) {
-                            continue;
-                        }
-                        pieces.push(trimmed.to_string());
+                if let ContentItem::InputText { text } = entry {
+                    if is_session_prefix(&text) {
+                        return None;
                     }
-                    _ => {}
+                    let trimmed = text.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    if looks_like_instructions(trimmed) {
+                        continue;
+                    }
+                    pieces.push(trimmed.to_string());
                 }
             }
             if pieces.is_empty() {
@@ -504,19 +1439,19 @@ fn preview_from_response_item(item: ResponseItem) -> Option<String> {
     }
 }
 
-fn flatten_content(content: &[ContentItem]) -> String {
+pub(crate) fn flatten_content(content: &[ContentItem]) -> String {
     let mut buf = String::new();
     for entry in content {
         match entry {
             ContentItem::InputText { text } | ContentItem::OutputText { text } => {
                 if !buf.is_empty() {
-                    buf.push_str("\n");
+                    buf.push('\n');
                 }
                 buf.push_str(text);
             }
             ContentItem::InputImage { image_url } => {
                 if !buf.is_empty() {
-                    buf.push_str("\n");
+                    buf.push('\n');
                 }
                 buf.push_str(&format!("[image: {image_url}]"));
             }
@@ -575,7 +1510,7 @@ fn build_cursor_from_path(path: &Path) -> Option<String> {
     Some(format!("{ts_str}|{uuid}"))
 }
 
-fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
+pub(crate) fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
     let core = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
     let (sep_idx, uuid) = core
         .match_indices('-')
@@ -596,12 +1531,11 @@ where
     let mut entries: Vec<(T, PathBuf)> = Vec::new();
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            if let Some(name) = entry.file_name().to_str() {
-                if let Some(parsed) = parse(name) {
-                    entries.push((parsed, entry.path()));
-                }
-            }
+        if entry.file_type()?.is_dir()
+            && let Some(name) = entry.file_name().to_str()
+            && let Some(parsed) = parse(name)
+        {
+            entries.push((parsed, entry.path()));
         }
     }
     entries.sort_by_key(|(val, _)| Reverse(*val));
@@ -612,22 +1546,21 @@ fn collect_rollout_files(dir: &Path) -> io::Result<Vec<(OffsetDateTime, Uuid, Pa
     let mut files = Vec::new();
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
-        if entry.file_type()?.is_file() {
-            if let Some(name) = entry.file_name().to_str() {
-                if let Some((ts, uuid)) = parse_timestamp_uuid_from_filename(name) {
-                    files.push((ts, uuid, entry.path()));
-                }
-            }
+        if entry.file_type()?.is_file()
+            && let Some(name) = entry.file_name().to_str()
+            && let Some((ts, uuid)) = parse_timestamp_uuid_from_filename(name)
+        {
+            files.push((ts, uuid, entry.path()));
         }
     }
     Ok(files)
 }
 
-fn paths_match(a: &Path, b: &Path) -> bool {
-    match (a.canonicalize(), b.canonicalize()) {
-        (Ok(ca), Ok(cb)) => ca == cb,
-        _ => a == b,
-    }
+/// Resolved form of a path; once it no longer exists, at least an absolute one.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
 }
 
 fn export_markdown_pdf(markdown: &str, target: &Path) -> Result<()> {
@@ -658,3 +1591,69 @@ fn export_markdown_pdf(markdown: &str, target: &Path) -> Result<()> {
     writer.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Paths under a root that does not exist, so canonicalizing leaves them as they are.
+    fn dir(path: &str) -> PathBuf {
+        PathBuf::from("/codex-session-test").join(path)
+    }
+
+    fn glob_scope(pattern: &str, recursive: bool) -> CwdScope {
+        CwdScope::Glob {
+            pattern: glob::Pattern::new(&format!("/codex-session-test/{pattern}")).unwrap(),
+            recursive,
+        }
+    }
+
+    #[test]
+    fn sessions_without_a_cwd_only_match_every_project() {
+        assert!(CwdScope::All.matches(None));
+        assert!(!CwdScope::Exact(vec![dir("app")]).matches(None));
+        assert!(!CwdScope::Within(vec![dir("app")]).matches(None));
+        assert!(!glob_scope("*", true).matches(None));
+    }
+
+    #[test]
+    fn exact_and_within_scopes() {
+        let exact = CwdScope::Exact(vec![dir("app"), dir("app-worktree")]);
+        assert!(exact.matches(Some(&dir("app"))));
+        assert!(exact.matches(Some(&dir("app-worktree"))));
+        assert!(!exact.matches(Some(&dir("app/src"))));
+
+        let within = CwdScope::Within(vec![dir("app")]);
+        assert!(within.matches(Some(&dir("app"))));
+        assert!(within.matches(Some(&dir("app/src/deep"))));
+        assert!(!within.matches(Some(&dir("application"))));
+    }
+
+    #[test]
+    fn glob_scopes_match_whole_components() {
+        let flat = glob_scope("*/src", false);
+        assert!(flat.matches(Some(&dir("app/src"))));
+        assert!(!flat.matches(Some(&dir("app/src/deep"))));
+        assert!(!flat.matches(Some(&dir("team/app/src"))));
+    }
+
+    #[test]
+    fn recursive_glob_scopes_match_subdirectories() {
+        let recursive = glob_scope("app-*", true);
+        assert!(recursive.matches(Some(&dir("app-2"))));
+        assert!(recursive.matches(Some(&dir("app-2/src/deep"))));
+        assert!(!recursive.matches(Some(&dir("app"))));
+        assert!(!glob_scope("app-*", false).matches(Some(&dir("app-2/src"))));
+    }
+
+    #[test]
+    fn path_remap_prefers_the_most_specific_entry() {
+        let remap = PathRemap::new([(dir("old"), dir("new")), (dir("old/app"), dir("moved/app"))]);
+        assert_eq!(remap.apply(&dir("old/app/src")), Some(dir("moved/app/src")));
+        assert_eq!(remap.apply(&dir("old/app")), Some(dir("moved/app")));
+        assert_eq!(remap.apply(&dir("old/lib")), Some(dir("new/lib")));
+        assert_eq!(remap.apply(&dir("old")), Some(dir("new")));
+        assert_eq!(remap.apply(&dir("older")), None);
+        assert_eq!(remap.apply(&dir("other")), None);
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{Datelike, Local, Timelike};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::pricing::PriceTable;
+use crate::session_store::SessionSummary;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Bucket {
+    pub key: String,
+    pub sessions: usize,
+    pub turns: usize,
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageStats {
+    pub sessions: usize,
+    pub turns: usize,
+    pub tokens: u64,
+    pub estimated_cost: f64,
+    pub median_duration_secs: Option<i64>,
+    pub per_day: Vec<Bucket>,
+    pub per_week: Vec<Bucket>,
+    pub per_project: Vec<Bucket>,
+    pub per_branch: Vec<Bucket>,
+    pub per_provider: Vec<Bucket>,
+    pub per_model: Vec<Bucket>,
+    pub busiest_hours: Vec<Bucket>,
+}
+
+/// Aggregate sessions into totals and per-dimension breakdowns (dates use local time).
+pub fn compute(sessions: &[SessionSummary], prices: &PriceTable) -> UsageStats {
+    let mut per_day = Tally::default();
+    let mut per_week = Tally::default();
+    let mut per_hour = Tally::default();
+    let mut per_project = Tally::default();
+    let mut per_branch = Tally::default();
+    let mut per_provider = Tally::default();
+    let mut per_model = Tally::default();
+    let mut turns = 0;
+    let mut tokens = 0;
+    let mut estimated_cost = 0.0;
+    let mut durations: Vec<i64> = Vec::new();
+
+    for summary in sessions {
+        if let Some(started) = summary.created_at.or(summary.updated_at) {
+            let local = started.with_timezone(&Local);
+            let week = local.iso_week();
+            per_day.add(local.format("%Y-%m-%d").to_string(), summary);
+            per_week.add(format!("{}-W{:02}", week.year(), week.week()), summary);
+            per_hour.add(format!("{:02}:00", local.hour()), summary);
+        }
+        per_project.add(
+            summary
+                .cwd
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "(unknown)".into()),
+            summary,
+        );
+        per_branch.add(label(summary.git_branch.as_deref(), "(none)"), summary);
+        per_provider.add(label(summary.provider.as_deref(), "(unknown)"), summary);
+        per_model.add(label(summary.model.as_deref(), "(unknown)"), summary);
+
+        turns += summary.turns;
+        if let Some(usage) = summary.tokens.as_ref() {
+            tokens += usage.total;
+            estimated_cost += prices
+                .estimate(summary.model.as_deref(), usage)
+                .unwrap_or(0.0);
+        }
+        if let Some(duration) = summary.duration() {
+            durations.push(duration.num_seconds().max(0));
+        }
+    }
+
+    UsageStats {
+        sessions: sessions.len(),
+        turns,
+        tokens,
+        estimated_cost,
+        median_duration_secs: median(&mut durations),
+        per_day: per_day.chronological(),
+        per_week: per_week.chronological(),
+        per_project: per_project.busiest(),
+        per_branch: per_branch.busiest(),
+        per_provider: per_provider.busiest(),
+        per_model: per_model.busiest(),
+        busiest_hours: per_hour.busiest(),
+    }
+}
+
+fn label(value: Option<&str>, fallback: &str) -> String {
+    value.unwrap_or(fallback).to_string()
+}
+
+fn median(values: &mut [i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[mid - 1] + values[mid]) / 2)
+    } else {
+        Some(values[mid])
+    }
+}
+
+#[derive(Default)]
+struct Tally(HashMap<String, Bucket>);
+
+impl Tally {
+    fn add(&mut self, key: String, summary: &SessionSummary) {
+        let bucket = self.0.entry(key.clone()).or_insert_with(|| Bucket {
+            key,
+            ..Bucket::default()
+        });
+        bucket.sessions += 1;
+        bucket.turns += summary.turns;
+        bucket.tokens += summary.tokens.map(|usage| usage.total).unwrap_or(0);
+    }
+
+    fn chronological(self) -> Vec<Bucket> {
+        let mut buckets: Vec<Bucket> = self.0.into_values().collect();
+        buckets.sort_by(|a, b| a.key.cmp(&b.key));
+        buckets
+    }
+
+    fn busiest(self) -> Vec<Bucket> {
+        let mut buckets: Vec<Bucket> = self.0.into_values().collect();
+        buckets.sort_by(|a, b| {
+            b.sessions
+                .cmp(&a.sessions)
+                .then(b.tokens.cmp(&a.tokens))
+                .then(a.key.cmp(&b.key))
+        });
+        buckets
+    }
+}
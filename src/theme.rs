@@ -0,0 +1,143 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// Base palette the configured colours are layered on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Dark,
+    /// Avoids yellow and gray backgrounds that vanish on light terminals.
+    Light,
+}
+
+/// Colours overriding the preset; accepts names (`lightblue`), indexes (`42`) and `#rrggbb`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorOverrides {
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub accent: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub selected_fg: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub selected_bg: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub marked: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub range: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub danger: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<Color>,
+    #[serde(with = "color", skip_serializing_if = "Option::is_none")]
+    pub muted: Option<Color>,
+}
+
+/// Styles used across the TUI.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Title, snippet labels and other decorations.
+    pub accent: Style,
+    /// The row under the cursor.
+    pub selected: Style,
+    /// Rows in the multi-selection.
+    pub marked: Style,
+    pub pinned: Style,
+    /// Rows covered by a pending range selection.
+    pub range: Style,
+    /// Confirmation prompts for destructive actions.
+    pub danger: Style,
+    /// Matched text in search results.
+    pub highlight: Style,
+    /// Secondary text such as key hints.
+    pub muted: Style,
+}
+
+impl Theme {
+    pub fn new(preset: Preset, colors: &ColorOverrides) -> Self {
+        let base = Palette::of(preset);
+        let pick = |custom: Option<Color>, fallback: Color| custom.unwrap_or(fallback);
+        Self {
+            accent: Style::default().fg(pick(colors.accent, base.accent)),
+            selected: Style::default()
+                .fg(pick(colors.selected_fg, base.selected_fg))
+                .bg(pick(colors.selected_bg, base.selected_bg)),
+            marked: Style::default().fg(pick(colors.marked, base.marked)),
+            pinned: Style::default().fg(pick(colors.pinned, base.pinned)),
+            range: Style::default().bg(pick(colors.range, base.range)),
+            danger: Style::default().fg(pick(colors.danger, base.danger)),
+            highlight: Style::default()
+                .fg(pick(colors.highlight, base.highlight))
+                .add_modifier(Modifier::BOLD),
+            muted: Style::default().fg(pick(colors.muted, base.muted)),
+        }
+    }
+}
+
+struct Palette {
+    accent: Color,
+    selected_fg: Color,
+    selected_bg: Color,
+    marked: Color,
+    pinned: Color,
+    range: Color,
+    danger: Color,
+    highlight: Color,
+    muted: Color,
+}
+
+impl Palette {
+    fn of(preset: Preset) -> Self {
+        match preset {
+            Preset::Dark => Self {
+                accent: Color::Cyan,
+                selected_fg: Color::Black,
+                selected_bg: Color::Cyan,
+                marked: Color::Yellow,
+                pinned: Color::Magenta,
+                range: Color::DarkGray,
+                danger: Color::Red,
+                highlight: Color::Yellow,
+                muted: Color::Gray,
+            },
+            Preset::Light => Self {
+                accent: Color::Blue,
+                selected_fg: Color::White,
+                selected_bg: Color::Blue,
+                marked: Color::Indexed(130),
+                pinned: Color::Magenta,
+                range: Color::Indexed(252),
+                danger: Color::Red,
+                highlight: Color::Indexed(130),
+                muted: Color::DarkGray,
+            },
+        }
+    }
+}
+
+mod color {
+    use ratatui::style::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(
+        color: &Option<Color>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match color {
+            Some(color) => serializer.serialize_str(&color.to_string().to_ascii_lowercase()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Color>, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Color::from_str(&raw)
+            .map(Some)
+            .map_err(|_| de::Error::custom(format!("unknown colour `{raw}`")))
+    }
+}
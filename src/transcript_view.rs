@@ -0,0 +1,110 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::session_store::ChatEntry;
+
+/// Render chat entries as styled lines for the TUI transcript pane.
+pub fn render_transcript(entries: &[ChatEntry]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for entry in entries {
+        let content = entry.text().trim_end();
+        lines.push(Line::from(Span::styled(
+            format!("▌ {}", entry.label().to_uppercase()),
+            Style::default()
+                .fg(entry_color(entry))
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        match entry {
+            ChatEntry::Message { .. } => render_markdownish(content, &mut lines),
+            ChatEntry::Reasoning { .. } => {
+                for raw in content.lines() {
+                    lines.push(Line::from(Span::styled(
+                        raw.to_string(),
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::ITALIC),
+                    )));
+                }
+            }
+            ChatEntry::ToolCall { .. } | ChatEntry::WebSearch { .. } => {
+                lines.push(Line::from(Span::styled(
+                    format!("$ {content}"),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+            ChatEntry::ToolOutput { omitted_lines, .. } => {
+                for raw in content.lines() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {raw}"),
+                        Style::default().fg(Color::Gray),
+                    )));
+                }
+                if let Some(count) = omitted_lines {
+                    lines.push(Line::from(Span::styled(
+                        format!("  … {count} more lines"),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+        }
+        lines.push(Line::default());
+    }
+    lines
+}
+
+fn render_markdownish(content: &str, lines: &mut Vec<Line<'static>>) {
+    let mut in_code = false;
+    for raw in content.lines() {
+        if raw.trim_start().starts_with("```") {
+            in_code = !in_code;
+            lines.push(Line::from(Span::styled(
+                raw.to_string(),
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else if in_code {
+            lines.push(Line::from(Span::styled(
+                format!("  {raw}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        } else if raw.starts_with('#') {
+            lines.push(Line::from(Span::styled(
+                raw.to_string(),
+                Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            )));
+        } else {
+            lines.push(render_inline(raw));
+        }
+    }
+}
+
+fn entry_color(entry: &ChatEntry) -> Color {
+    match entry {
+        ChatEntry::Message { role, .. } => match role.as_str() {
+            "user" => Color::Green,
+            "assistant" => Color::Cyan,
+            "system" | "developer" => Color::Magenta,
+            _ => Color::Yellow,
+        },
+        ChatEntry::Reasoning { .. } => Color::Magenta,
+        ChatEntry::ToolCall { .. } | ChatEntry::WebSearch { .. } => Color::Blue,
+        ChatEntry::ToolOutput { .. } => Color::DarkGray,
+    }
+}
+
+/// Highlight `inline code` spans; everything else is kept verbatim.
+fn render_inline(text: &str) -> Line<'static> {
+    let spans: Vec<Span<'static>> = text
+        .split('`')
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+        .map(|(idx, part)| {
+            if idx % 2 == 1 {
+                Span::styled(part.to_string(), Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw(part.to_string())
+            }
+        })
+        .collect();
+    Line::from(spans)
+}
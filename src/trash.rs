@@ -0,0 +1,140 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::age_cutoff;
+use crate::session_index::STATE_SUBDIR;
+use crate::session_store::parse_timestamp_uuid_from_filename;
+
+const TRASH_SUBDIR: &str = "trash";
+const META_EXTENSION: &str = "trash.json";
+
+/// A rollout moved into the trash, with enough metadata to put it back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub original_path: PathBuf,
+    pub deleted_at: DateTime<Utc>,
+    /// Location of the rollout inside the trash directory.
+    #[serde(skip)]
+    pub file: PathBuf,
+}
+
+pub fn trash_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join(STATE_SUBDIR).join(TRASH_SUBDIR)
+}
+
+/// Move a rollout into the trash and record where it came from.
+pub fn trash_session(codex_home: &Path, path: &Path) -> Result<TrashEntry> {
+    let dir = trash_dir(codex_home);
+    fs::create_dir_all(&dir).with_context(|| format!("unable to create trash {dir:?}"))?;
+    let original_path =
+        fs::canonicalize(path).with_context(|| format!("failed to resolve {path:?}"))?;
+    let file_name = original_path
+        .file_name()
+        .context("session path has no file name")?;
+    let file = dir.join(file_name);
+    if file.exists() {
+        bail!("{} is already in the trash", file.display());
+    }
+
+    let entry = TrashEntry {
+        id: file_name
+            .to_str()
+            .and_then(parse_timestamp_uuid_from_filename)
+            .map(|(_, uuid)| uuid.to_string())
+            .unwrap_or_else(|| file_name.to_string_lossy().into_owned()),
+        original_path,
+        deleted_at: Utc::now(),
+        file,
+    };
+    // Write the metadata first so a rollout is never stranded in the trash without it.
+    let meta_path = meta_path(&entry.file);
+    let mut writer = BufWriter::new(
+        File::create(&meta_path).with_context(|| format!("failed to create {meta_path:?}"))?,
+    );
+    serde_json::to_writer_pretty(&mut writer, &entry)?;
+    writer.flush()?;
+    if let Err(err) = fs::rename(&entry.original_path, &entry.file) {
+        let _ = fs::remove_file(&meta_path);
+        return Err(err)
+            .with_context(|| format!("failed to move {:?} to the trash", entry.original_path));
+    }
+    Ok(entry)
+}
+
+/// Put a trashed rollout back at its original location.
+pub fn restore(entry: &TrashEntry) -> Result<PathBuf> {
+    let target = &entry.original_path;
+    if target.exists() {
+        bail!("{} already exists", target.display());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory {parent:?}"))?;
+    }
+    fs::rename(&entry.file, target)
+        .with_context(|| format!("failed to restore {:?}", entry.file))?;
+    let _ = fs::remove_file(meta_path(&entry.file));
+    Ok(target.clone())
+}
+
+/// Permanently remove a trashed rollout and its metadata.
+pub fn remove(entry: &TrashEntry) -> Result<()> {
+    fs::remove_file(&entry.file).with_context(|| format!("failed to remove {:?}", entry.file))?;
+    let _ = fs::remove_file(meta_path(&entry.file));
+    Ok(())
+}
+
+/// Every entry in the trash, most recently deleted first.
+pub fn list(codex_home: &Path) -> Result<Vec<TrashEntry>> {
+    let dir = trash_dir(codex_home);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for dir_entry in fs::read_dir(&dir).with_context(|| format!("failed to read {dir:?}"))? {
+        let path = dir_entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(file_name) = name.strip_suffix(&format!(".{META_EXTENSION}")) else {
+            continue;
+        };
+        let Ok(file) = File::open(&path) else {
+            continue;
+        };
+        let Ok(mut entry) = serde_json::from_reader::<_, TrashEntry>(BufReader::new(file)) else {
+            continue;
+        };
+        entry.file = dir.join(file_name);
+        if entry.file.exists() {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by_key(|entry| Reverse(entry.deleted_at));
+    Ok(entries)
+}
+
+/// Permanently remove entries deleted longer than `max_age` ago, returning how many went.
+pub fn purge_older_than(codex_home: &Path, max_age: Duration) -> Result<usize> {
+    let cutoff = age_cutoff(Utc::now(), max_age);
+    let mut purged = 0;
+    for entry in list(codex_home)? {
+        if entry.deleted_at < cutoff {
+            remove(&entry)?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+fn meta_path(file: &Path) -> PathBuf {
+    let mut name = file.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{META_EXTENSION}"));
+    file.with_file_name(name)
+}
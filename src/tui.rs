@@ -1,8 +1,10 @@
+use std::cell::Cell;
+use std::collections::HashSet;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use crossterm::event::{self, Event as CEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
@@ -11,20 +13,79 @@ use crossterm::terminal::{
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, Wrap};
 
-use crate::session_store::{SessionSummary, export_session_chat};
+use crate::annotations::AnnotationStore;
+use crate::archive::archive_session;
+use crate::columns::Column;
+use crate::config::{self, Config, ExportConfig, Scope, ViewConfig};
+use crate::git::GitChecker;
+use crate::keymap::{Action, Keymap};
+use crate::launcher::{ResumeCommand, ResumeLauncher};
+use crate::pricing::PriceTable;
+use crate::search::{Snippet, search_transcript};
+use crate::session_store::{
+    CwdScope, EntryKind, ExportOptions, SessionSummary, SortKey, TurnSettings, export_session_chat,
+    read_session_entries, read_turn_settings, sort_sessions,
+};
+use crate::theme::Theme;
+use crate::transcript_view::render_transcript;
+use crate::trash::{self, TrashEntry};
 
 const DELETE_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(600);
+const FULLTEXT_SNIPPETS: usize = 2;
+const TRANSCRIPT_MAX_OUTPUT_LINES: usize = 20;
+/// Actions advertised in the title bar; `?` lists the rest.
+const TITLE_HINTS: [Action; 9] = [
+    Action::Open,
+    Action::Filter,
+    Action::Find,
+    Action::Transcript,
+    Action::Scope,
+    Action::Mark,
+    Action::Delete,
+    Action::Help,
+    Action::Quit,
+];
+/// Actions advertised below the full-screen transcript.
+const TRANSCRIPT_HINTS: [Action; 7] = [
+    Action::Down,
+    Action::Up,
+    Action::PageDown,
+    Action::PageUp,
+    Action::Top,
+    Action::Bottom,
+    Action::Back,
+];
+/// Actions advertised in the column picker's title.
+const COLUMN_HINTS: [Action; 2] = [Action::Toggle, Action::Back];
+/// Headings and actions of the `?` overlay.
+const HELP_SECTIONS: [(&str, &[Action]); 4] = [
+    ("Session list", &Action::LIST),
+    ("Transcript", &TRANSCRIPT_HINTS),
+    (
+        "Column picker",
+        &[Action::Up, Action::Down, Action::Toggle, Action::Back],
+    ),
+    ("Session dialog", &Action::PROMPT),
+];
 
 pub enum TuiOutcome {
-    Resume(SessionSummary),
-    Jump(SessionSummary),
+    Resume(SessionSummary, ResumeCommand),
+    Jump(SessionSummary, ResumeCommand),
 }
 
-pub fn run(sessions: Vec<SessionSummary>) -> Result<Option<TuiOutcome>> {
+/// `project` is the directory scope the list starts in unless the config asks for every project.
+pub fn run(
+    codex_home: &Path,
+    sessions: Vec<SessionSummary>,
+    project: CwdScope,
+    prices: PriceTable,
+    launcher: &ResumeLauncher,
+    config: &Config,
+) -> Result<Option<TuiOutcome>> {
     if sessions.is_empty() {
         println!("No Codex sessions recorded yet. Start a session to manage history.");
         return Ok(None);
@@ -37,35 +98,48 @@ pub fn run(sessions: Vec<SessionSummary>) -> Result<Option<TuiOutcome>> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let mut app = App::new(sessions);
-    let mut outcome = None;
+    let mut app = App::new(
+        codex_home.to_path_buf(),
+        sessions,
+        project,
+        prices,
+        launcher.clone(),
+        config,
+    );
+    // Restore the terminal before reporting any error from the event loop.
+    let outcome = event_loop(&mut terminal, &mut app);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    outcome
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+) -> Result<Option<TuiOutcome>> {
     loop {
+        app.check_git();
         terminal.draw(|f| app.draw(f))?;
 
         if crossterm::event::poll(Duration::from_millis(200))? {
             match event::read()? {
                 CEvent::Key(key) if key.kind == KeyEventKind::Press => match app.handle_key(key)? {
                     AppAction::None => {}
-                    AppAction::Quit => break,
-                    AppAction::Resume(summary) => {
-                        outcome = Some(TuiOutcome::Resume(summary));
-                        break;
+                    AppAction::Quit => return Ok(None),
+                    AppAction::Resume(summary, command) => {
+                        return Ok(Some(TuiOutcome::Resume(summary, command)));
                     }
-                    AppAction::Jump(path) => {
-                        outcome = Some(TuiOutcome::Jump(path));
-                        break;
+                    AppAction::Jump(summary, command) => {
+                        return Ok(Some(TuiOutcome::Jump(summary, command)));
                     }
                 },
                 _ => {}
             }
         }
     }
-
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
-
-    Ok(outcome)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -73,52 +147,171 @@ enum Mode {
     Normal,
     Search,
     Command,
+    EditResume,
     ActionPrompt,
     ConfirmDelete,
+    FullTextInput,
+    FullTextResults,
+    Transcript,
+    Columns,
+    Help,
+}
+
+struct TranscriptView {
+    path: PathBuf,
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+}
+
+struct FullTextHit {
+    session_idx: usize,
+    snippets: Vec<Snippet>,
 }
 
 struct App {
+    codex_home: PathBuf,
     sessions: Vec<SessionSummary>,
     filtered: Vec<usize>,
     selected: usize,
     query: String,
     command: String,
+    /// Resume command line being edited before launch.
+    resume_line: String,
+    /// Cursor position in `resume_line`, in chars.
+    resume_cursor: usize,
+    /// Last recorded turn settings of the session in the action dialog.
+    original: Option<TurnSettings>,
     mode: Mode,
     delete_primed_at: Option<Instant>,
     status: Option<String>,
+    fulltext_query: String,
+    fulltext_hits: Vec<FullTextHit>,
+    fulltext_selected: usize,
+    split_view: bool,
+    transcript: Option<TranscriptView>,
+    transcript_height: Cell<u16>,
+    /// Rows the transcript takes once wrapped to the pane, recorded by the last draw.
+    transcript_rows: Cell<u16>,
+    prices: PriceTable,
+    sort_key: SortKey,
+    columns: Vec<Column>,
+    /// Highlighted entry of the column picker, indexing `Column::ALL`.
+    column_cursor: usize,
+    /// Keep sessions of the same repository together, in sort order within each group.
+    group_by_repo: bool,
+    /// Number of table rows that fit on screen, recorded by the last draw.
+    table_rows: Cell<u16>,
+    git: GitChecker,
+    /// Rollout paths whose git state has been checked already.
+    git_checked: HashSet<PathBuf>,
+    /// The directories of the current project, shown while `scoped` is set.
+    project: CwdScope,
+    /// Rollout paths of the sessions recorded inside `project`.
+    in_project: HashSet<PathBuf>,
+    scoped: bool,
+    /// The batch removed by the last delete, restored together by undo.
+    last_deleted: Vec<(TrashEntry, SessionSummary)>,
+    /// Rollout paths of the multi-selected sessions.
+    marked: HashSet<PathBuf>,
+    /// Visible row where a pending `V` range selection started.
+    range_anchor: Option<usize>,
+    config_path: PathBuf,
+    export: ExportConfig,
+    launcher: ResumeLauncher,
+    theme: Theme,
+    keymap: Keymap,
 }
 
 enum AppAction {
     None,
     Quit,
-    Resume(SessionSummary),
-    Jump(SessionSummary),
+    Resume(SessionSummary, ResumeCommand),
+    Jump(SessionSummary, ResumeCommand),
 }
 
 impl App {
-    fn new(sessions: Vec<SessionSummary>) -> Self {
+    fn new(
+        codex_home: PathBuf,
+        mut sessions: Vec<SessionSummary>,
+        project: CwdScope,
+        prices: PriceTable,
+        launcher: ResumeLauncher,
+        config: &Config,
+    ) -> Self {
+        let view = config.view.clone();
+        sort_sessions(&mut sessions, view.sort);
+        // Resolving every CWD is slow enough to do once rather than on each keystroke.
+        let in_project = sessions
+            .iter()
+            .filter(|session| project.matches(session.cwd.as_deref()))
+            .map(|session| session.path.clone())
+            .collect();
         let mut app = Self {
+            codex_home,
             sessions,
             filtered: Vec::new(),
             selected: 0,
             query: String::new(),
             command: String::new(),
+            resume_line: String::new(),
+            resume_cursor: 0,
+            original: None,
             mode: Mode::Normal,
             delete_primed_at: None,
             status: None,
+            fulltext_query: String::new(),
+            fulltext_hits: Vec::new(),
+            fulltext_selected: 0,
+            split_view: false,
+            transcript: None,
+            transcript_height: Cell::new(0),
+            transcript_rows: Cell::new(0),
+            prices,
+            sort_key: view.sort,
+            columns: view.columns,
+            column_cursor: 0,
+            group_by_repo: view.group_by_repo,
+            table_rows: Cell::new(0),
+            git: GitChecker::default(),
+            git_checked: HashSet::new(),
+            project,
+            in_project,
+            scoped: config.list.scope != Scope::All,
+            last_deleted: Vec::new(),
+            marked: HashSet::new(),
+            range_anchor: None,
+            config_path: config.path.clone(),
+            export: config.export.clone(),
+            launcher,
+            theme: Theme::new(config.tui.theme, &config.tui.colors),
+            keymap: Keymap::new(&config.tui.keys),
         };
         app.apply_filter();
         app
     }
 
     fn apply_filter(&mut self) {
+        // Visible row numbers shift with the filter, so a pending range no longer applies.
+        self.range_anchor = None;
         self.filtered = self
             .sessions
             .iter()
             .enumerate()
-            .filter(|(_, session)| self.matches_query(session))
+            .filter(|(_, session)| {
+                (!self.scoped || self.in_project.contains(&session.path))
+                    && self.matches_query(session)
+            })
             .map(|(idx, _)| idx)
             .collect();
+        if self.group_by_repo {
+            // Stable, so each group keeps the current sort order; pinned sessions stay on
+            // top and sessions without a repository come last.
+            self.filtered.sort_by_cached_key(|&idx| {
+                let session = &self.sessions[idx];
+                let repo = session.repo_label();
+                (!session.pinned, repo.is_none(), repo)
+            });
+        }
         if self.filtered.is_empty() {
             self.selected = 0;
         } else if self.selected >= self.filtered.len() {
@@ -129,14 +322,25 @@ impl App {
     fn matches_query(&self, summary: &SessionSummary) -> bool {
         if self.query.is_empty() {
             true
+        } else if let Some(tag) = self.query.strip_prefix('#') {
+            let tag = tag.to_ascii_lowercase();
+            summary
+                .tags
+                .iter()
+                .any(|candidate| candidate.to_ascii_lowercase().starts_with(&tag))
         } else {
             let needle = self.query.to_ascii_lowercase();
             summary.id.to_ascii_lowercase().contains(&needle)
                 || summary
-                    .preview
-                    .as_deref()
-                    .map(|p| p.to_ascii_lowercase().contains(&needle))
-                    .unwrap_or(false)
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_ascii_lowercase().contains(&needle))
+                || summary
+                    .title
+                    .iter()
+                    .chain(summary.auto_title.iter())
+                    .chain(summary.preview.iter())
+                    .any(|text| text.to_ascii_lowercase().contains(&needle))
                 || summary
                     .cwd
                     .as_ref()
@@ -160,82 +364,184 @@ impl App {
         .split(frame.area());
 
         let title = Line::from(vec![
-            Span::styled("Codex Sessions", Style::default().fg(Color::Cyan)),
-            Span::raw("  (enter=resume, /=search, :export PATH, dd=delete, q=quit)"),
+            Span::styled("Codex Sessions", self.theme.accent),
+            Span::raw(format!(" · {}", self.scope_label())),
+            Span::styled(
+                format!("  ({})", self.keymap.hint(&TITLE_HINTS)),
+                self.theme.muted,
+            ),
         ]);
         frame.render_widget(title, layout[0]);
 
         let search_prompt = match self.mode {
             Mode::Search => format!("/{}", self.query),
             Mode::Command => format!(":{}", self.command),
-            _ => format!("{} sessions", self.filtered.len()),
+            Mode::EditResume => {
+                // Scroll the line sideways so the cursor stays on screen.
+                let prompt = format!("$ {}", self.resume_line);
+                let column = 2 + self.resume_cursor;
+                let skip = (column + 1).saturating_sub(usize::from(layout[1].width));
+                frame.set_cursor_position((layout[1].x + (column - skip) as u16, layout[1].y));
+                prompt.chars().skip(skip).collect()
+            }
+            Mode::FullTextInput => format!("find: {}", self.fulltext_query),
+            Mode::Transcript => self.keymap.hint(&TRANSCRIPT_HINTS),
+            _ if self.range_anchor.is_some() => format!(
+                "range: move with {}/{}, {}=select range, esc=cancel",
+                self.keymap.label(Action::Up),
+                self.keymap.label(Action::Down),
+                self.keymap.label(Action::Range)
+            ),
+            _ if !self.marked.is_empty() => format!(
+                "{} sessions · {} selected · sorted by {}{}",
+                self.filtered.len(),
+                self.marked.len(),
+                self.sort_key,
+                self.grouping_hint()
+            ),
+            _ => format!(
+                "{} sessions · sorted by {}{}",
+                self.filtered.len(),
+                self.sort_key,
+                self.grouping_hint()
+            ),
         };
         frame.render_widget(Line::from(search_prompt), layout[1]);
 
-        let rows: Vec<Row> = self
+        // Borders and the header row take three lines.
+        self.table_rows.set(layout[2].height.saturating_sub(3));
+        let pending_range = self
+            .range_anchor
+            .map(|anchor| (anchor.min(self.selected), anchor.max(self.selected)));
+        let mut previous_group = None;
+        let mut rows: Vec<Row> = self
             .filtered
             .iter()
             .enumerate()
             .map(|(visible_idx, &orig_idx)| {
                 let summary = &self.sessions[orig_idx];
-                let cwd = summary
-                    .cwd
-                    .as_ref()
-                    .map(|p| crate::shorten_path(p, 28))
-                    .unwrap_or_else(|| "(unknown)".into());
-                let preview = summary
-                    .preview
-                    .as_deref()
-                    .map(crate::truncate_preview)
-                    .unwrap_or_else(|| String::from("(no user input)"));
-                let updated = summary
-                    .updated_at
-                    .map(crate::format_relative)
-                    .unwrap_or_else(|| "unknown".into());
-                let mut row = Row::new(vec![
-                    updated,
-                    summary.git_branch.as_deref().unwrap_or("-").to_string(),
-                    cwd,
-                    preview,
-                ]);
+                let marked = self.marked.contains(&summary.path);
+                let flags = format!(
+                    "{}{}{}",
+                    if marked { "●" } else { " " },
+                    if summary.pinned { "★" } else { " " },
+                    if summary.git_status.is_some_and(|status| status.is_stale()) {
+                        "!"
+                    } else {
+                        " "
+                    }
+                );
+                // The repository name heads its group and is left blank on the rows below.
+                let group = self.group_by_repo.then(|| {
+                    let label = summary
+                        .repo_label()
+                        .unwrap_or_else(|| String::from("(no repository)"));
+                    let key = (summary.pinned, label);
+                    if previous_group.as_ref() == Some(&key) {
+                        String::new()
+                    } else {
+                        let label = key.1.clone();
+                        previous_group = Some(key);
+                        label
+                    }
+                });
+                let mut row = Row::new(
+                    std::iter::once(flags).chain(group).chain(
+                        self.columns
+                            .iter()
+                            .map(|column| column.cell(summary, &self.prices)),
+                    ),
+                );
+                let in_range =
+                    pending_range.is_some_and(|(start, end)| (start..=end).contains(&visible_idx));
                 if visible_idx == self.selected {
-                    row = row.style(Style::default().fg(Color::Black).bg(Color::Cyan));
+                    row = row.style(self.theme.selected);
+                } else if in_range {
+                    row = row.style(self.theme.range);
+                } else if marked {
+                    row = row.style(self.theme.marked);
+                } else if summary.pinned {
+                    row = row.style(self.theme.pinned);
                 }
                 row
             })
             .collect();
+        // Pinned sessions sort first; a rule separates them from the rest.
+        let pinned = self
+            .filtered
+            .iter()
+            .take_while(|&&idx| self.sessions[idx].pinned)
+            .count();
+        if pinned > 0 && pinned < self.filtered.len() {
+            let cells = 1 + usize::from(self.group_by_repo) + self.columns.len();
+            let rule = Row::new(vec!["─".repeat(usize::from(layout[2].width)); cells])
+                .style(self.theme.muted);
+            rows.insert(pinned, rule);
+        }
 
-        let header = Row::new(vec!["Updated", "Branch", "CWD", "Conversation"])
-            .style(Style::default().add_modifier(Modifier::BOLD));
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(20),
-                Constraint::Length(12),
-                Constraint::Length(30),
-                Constraint::Min(10),
-            ],
+        let header = Row::new(
+            std::iter::once("")
+                .chain(self.group_by_repo.then_some("Repository"))
+                .chain(self.columns.iter().map(|column| column.header())),
         )
-        .header(header)
-        .column_spacing(2)
-        .block(Block::default().borders(Borders::ALL));
-        frame.render_widget(table, layout[2]);
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let widths = std::iter::once(Constraint::Length(3))
+            .chain(self.group_by_repo.then_some(Constraint::Length(20)))
+            .chain(self.columns.iter().map(|column| match column.width() {
+                Some(width) => Constraint::Length(width),
+                None => Constraint::Min(10),
+            }));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .block(Block::default().borders(Borders::ALL));
+        if self.mode == Mode::Transcript {
+            self.draw_transcript(frame, layout[2]);
+        } else if self.split_view {
+            let panes =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(layout[2]);
+            frame.render_widget(table, panes[0]);
+            self.draw_transcript(frame, panes[1]);
+        } else {
+            frame.render_widget(table, layout[2]);
+        }
 
         if let Some(status) = self.status.as_deref() {
             frame.render_widget(Line::from(status.to_string()), layout[3]);
         }
 
         if self.mode == Mode::ActionPrompt {
-            let area = centered_rect(70, 25, frame.area());
+            let area = centered_rect(70, 35, frame.area());
             let text = if let Some(session) = self.current_session() {
                 let cwd = session
                     .cwd
                     .as_ref()
                     .map(|p| p.display().to_string())
                     .unwrap_or_else(|| "(unknown)".to_string());
+                let (settings, match_option) = match self.original.as_ref() {
+                    Some(original) => (
+                        format!(
+                            "\nModel: {}\nApproval: {} · Sandbox: {}",
+                            original.model_label(),
+                            original.approval_policy.as_deref().unwrap_or("unknown"),
+                            original.sandbox_label()
+                        ),
+                        format!(
+                            "\n[{}] Resume with the original model and sandbox",
+                            self.keymap.label(Action::ResumeOriginal)
+                        ),
+                    ),
+                    None => (String::new(), String::new()),
+                };
                 format!(
-                    "Session: {}\nCWD: {}\n\n[r] Resume here\n[j] Jump to session folder then resume\n[Esc] Cancel",
-                    session.id, cwd
+                    "Session: {}\nCWD: {}{settings}\n\n[{}] Resume here\n[{}] Jump to session folder then resume\n[{}] Edit the command line, then resume{match_option}\n[{}] Cancel",
+                    session.id,
+                    cwd,
+                    self.keymap.label(Action::Resume),
+                    self.keymap.label(Action::Jump),
+                    self.keymap.label(Action::Edit),
+                    self.keymap.label(Action::Back)
                 )
             } else {
                 "No session selected".to_string()
@@ -249,86 +555,288 @@ impl App {
             frame.render_widget(block, area);
         } else if self.mode == Mode::ConfirmDelete {
             let area = centered_rect(60, 20, frame.area());
-            let session = self.current_session();
+            let targets = self.targets();
+            let subject = match targets.as_slice() {
+                [session] => format!("session {}", session.id),
+                _ => format!("{} selected sessions", targets.len()),
+            };
             let text = format!(
-                "Delete session {}?\nThis cannot be undone.\nPress y to confirm or n to cancel.",
-                session.map(|s| s.id.clone()).unwrap_or_default()
+                "Move {subject} to the trash?\nPress {} afterwards to undo.\nPress y to confirm or n to cancel.",
+                self.keymap.label(Action::Undo)
+            );
+            let block = Paragraph::new(text).style(self.theme.danger).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Confirm delete"),
             );
-            let block = Paragraph::new(text)
-                .style(Style::default().fg(Color::Red))
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title("Confirm delete"),
-                );
             frame.render_widget(Clear, area);
             frame.render_widget(block, area);
+        } else if self.mode == Mode::FullTextResults {
+            self.draw_fulltext_results(frame);
+        } else if self.mode == Mode::Columns {
+            self.draw_column_picker(frame);
+        } else if self.mode == Mode::Help {
+            self.draw_help(frame);
         }
     }
 
+    fn draw_help(&self, frame: &mut ratatui::Frame) {
+        let area = centered_rect(60, 80, frame.area());
+        let sections: Vec<(&str, Vec<(String, &str)>)> = HELP_SECTIONS
+            .iter()
+            .map(|(heading, actions)| (*heading, self.keymap.help_lines(actions)))
+            .collect();
+        let width = sections
+            .iter()
+            .flat_map(|(_, entries)| entries.iter())
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines: Vec<Line> = Vec::new();
+        for (heading, entries) in sections {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                heading,
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            lines.extend(entries.into_iter().map(|(keys, description)| {
+                Line::from(vec![
+                    Span::styled(format!("{keys:<width$}  "), self.theme.accent),
+                    Span::raw(description),
+                ])
+            }));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Commands: :export FILE, :tag NAME, :untag NAME, :title TEXT",
+            self.theme.muted,
+        )));
+        lines.push(Line::from(Span::styled(
+            "Esc clears a pending range or the selection; Ctrl+C quits immediately.",
+            self.theme.muted,
+        )));
+        lines.push(Line::from(Span::styled(
+            "! marks sessions whose branch was deleted or whose commit is no longer in HEAD.",
+            self.theme.muted,
+        )));
+        let block = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Keys (any key to close)"),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+    }
+
+    fn draw_column_picker(&self, frame: &mut ratatui::Frame) {
+        let area = centered_rect(40, 60, frame.area());
+        let lines: Vec<Line> = Column::ALL
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                let checked = if self.columns.contains(column) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                let text = format!("{checked} {}", column.header());
+                if idx == self.column_cursor {
+                    Line::from(Span::styled(text, self.theme.selected))
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+        let block = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Columns ({})", self.keymap.hint(&COLUMN_HINTS))),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+    }
+
+    fn draw_transcript(&self, frame: &mut ratatui::Frame, area: Rect) {
+        self.transcript_height.set(area.height.saturating_sub(2));
+        let title = self
+            .current_session()
+            .map(|session| format!("Transcript {}", session.id))
+            .unwrap_or_else(|| String::from("Transcript"));
+        let (lines, scroll) = match self.transcript.as_ref() {
+            Some(view) => (view.lines.clone(), view.scroll),
+            None => (Vec::new(), 0),
+        };
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+        // Counted without the block, against the width inside its borders.
+        let rows = paragraph.line_count(area.width.saturating_sub(2));
+        self.transcript_rows
+            .set(u16::try_from(rows).unwrap_or(u16::MAX));
+        let paragraph = paragraph
+            .scroll((scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn draw_fulltext_results(&self, frame: &mut ratatui::Frame) {
+        let area = centered_rect(90, 80, frame.area());
+        let lines_per_hit = 1 + FULLTEXT_SNIPPETS;
+        let visible = (area.height.saturating_sub(2) as usize / lines_per_hit).max(1);
+        let start = self.fulltext_selected.saturating_sub(visible - 1);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (offset, hit) in self
+            .fulltext_hits
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+        {
+            let summary = &self.sessions[hit.session_idx];
+            let header_style = if offset == self.fulltext_selected {
+                self.theme.selected
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            let preview = summary
+                .display_title()
+                .map(crate::truncate_preview)
+                .unwrap_or_else(|| String::from("(no user input)"));
+            lines.push(Line::from(Span::styled(
+                format!("{}  {}", summary.id, preview),
+                header_style,
+            )));
+            for snippet in &hit.snippets {
+                let (before, matched, after) = snippet.parts();
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  [{}] ", snippet.kind), self.theme.accent),
+                    Span::raw(before.to_string()),
+                    Span::styled(matched.to_string(), self.theme.highlight),
+                    Span::raw(after.to_string()),
+                ]));
+            }
+        }
+
+        let block =
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(
+                "{} sessions mention \"{}\" ({})",
+                self.fulltext_hits.len(),
+                self.fulltext_query,
+                self.keymap.hint(&[Action::Open, Action::Back])
+            )));
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> Result<AppAction> {
         self.status = None;
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
             return Ok(AppAction::Quit);
         }
-        match self.mode {
+        let action = match self.mode {
             Mode::Normal => self.handle_normal_mode(key),
             Mode::Search => self.handle_search_mode(key),
             Mode::Command => self.handle_command_mode(key),
+            Mode::EditResume => self.handle_edit_resume(key),
             Mode::ActionPrompt => self.handle_action_prompt(key),
             Mode::ConfirmDelete => self.handle_confirm_mode(key),
+            Mode::FullTextInput => self.handle_fulltext_input(key),
+            Mode::FullTextResults => self.handle_fulltext_results(key),
+            Mode::Transcript => self.handle_transcript_mode(key),
+            Mode::Columns => self.handle_columns_mode(key),
+            Mode::Help => {
+                self.mode = Mode::Normal;
+                Ok(AppAction::None)
+            }
+        }?;
+        if self.split_view || self.mode == Mode::Transcript {
+            self.sync_transcript();
         }
+        Ok(action)
     }
 
     fn handle_normal_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => Ok(AppAction::Quit),
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.move_selection_up();
-                Ok(AppAction::None)
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.move_selection_down();
-                Ok(AppAction::None)
-            }
-            KeyCode::Char('/') => {
+        // Esc first backs out of a pending range or selection, whatever quit is bound to.
+        if key.code == KeyCode::Esc && self.range_anchor.is_some() {
+            self.range_anchor = None;
+            return Ok(AppAction::None);
+        }
+        if key.code == KeyCode::Esc && !self.marked.is_empty() {
+            self.marked.clear();
+            self.status = Some(String::from("Selection cleared"));
+            return Ok(AppAction::None);
+        }
+        let Some(action) = self.keymap.action(&key, &Action::LIST) else {
+            self.delete_primed_at = None;
+            return Ok(AppAction::None);
+        };
+        match action {
+            Action::Quit => return Ok(AppAction::Quit),
+            Action::Mark => self.toggle_mark(),
+            Action::Range => self.mark_range(),
+            Action::MarkAll => self.mark_all_filtered(),
+            Action::Up => self.move_selection_up(),
+            Action::Down => self.move_selection_down(),
+            Action::Filter => {
                 self.mode = Mode::Search;
                 self.query.clear();
                 self.apply_filter();
-                Ok(AppAction::None)
             }
-            KeyCode::Char(':') => {
+            Action::Transcript => {
+                if self.current_session().is_some() {
+                    self.mode = Mode::Transcript;
+                }
+            }
+            Action::Split => self.split_view = !self.split_view,
+            Action::Sort => self.cycle_sort(),
+            Action::Columns => self.mode = Mode::Columns,
+            Action::Group => self.toggle_grouping(),
+            Action::Scope => self.toggle_scope(),
+            Action::Archive => self.archive_targets(),
+            Action::Pin => self.toggle_pin()?,
+            Action::Undo => self.undo_delete(),
+            Action::Find => {
+                self.mode = Mode::FullTextInput;
+                self.fulltext_query.clear();
+            }
+            Action::Command => {
                 self.mode = Mode::Command;
                 self.command.clear();
                 self.delete_primed_at = None;
-                Ok(AppAction::None)
             }
-            KeyCode::Enter => {
-                if self.current_session().is_some() {
+            Action::Help => self.mode = Mode::Help,
+            Action::Open => {
+                if let Some(session) = self.current_session() {
+                    self.original = read_turn_settings(&session.path).ok().flatten();
                     self.mode = Mode::ActionPrompt;
                 }
-                Ok(AppAction::None)
             }
-            KeyCode::Char('d') => {
+            Action::Edit => self.start_edit_resume(),
+            Action::Delete => {
                 let now = Instant::now();
-                if let Some(prime) = self.delete_primed_at {
-                    if now.duration_since(prime) <= DELETE_SEQUENCE_TIMEOUT {
-                        if self.current_session().is_some() {
-                            self.mode = Mode::ConfirmDelete;
-                        }
-                        self.delete_primed_at = None;
-                        return Ok(AppAction::None);
+                if let Some(prime) = self.delete_primed_at
+                    && now.duration_since(prime) <= DELETE_SEQUENCE_TIMEOUT
+                {
+                    if !self.targets().is_empty() {
+                        self.mode = Mode::ConfirmDelete;
                     }
+                    self.delete_primed_at = None;
+                    return Ok(AppAction::None);
                 }
                 self.delete_primed_at = Some(now);
-                self.status = Some(String::from("Press d again to delete the selected session"));
-                Ok(AppAction::None)
-            }
-            _ => {
-                self.delete_primed_at = None;
-                Ok(AppAction::None)
+                self.status = Some(format!(
+                    "Press {} again to delete the {}",
+                    self.keymap.label(Action::Delete),
+                    if self.marked.is_empty() {
+                        "highlighted session"
+                    } else {
+                        "selected sessions"
+                    }
+                ));
             }
         }
+        Ok(AppAction::None)
     }
 
     fn handle_search_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
@@ -348,11 +856,9 @@ impl App {
             }
             KeyCode::Char('j') if key.modifiers.is_empty() => self.move_selection_down(),
             KeyCode::Char('k') if key.modifiers.is_empty() => self.move_selection_up(),
-            KeyCode::Char(c) => {
-                if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.query.push(c);
-                    self.apply_filter();
-                }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(c);
+                self.apply_filter();
             }
             KeyCode::Down => self.move_selection_down(),
             KeyCode::Up => self.move_selection_up(),
@@ -361,22 +867,295 @@ impl App {
         Ok(AppAction::None)
     }
 
-    fn handle_action_prompt(&mut self, key: KeyEvent) -> Result<AppAction> {
+    fn handle_transcript_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
+        let page = self.transcript_height.get().max(1);
+        match self.keymap.action(&key, &Action::TRANSCRIPT) {
+            Some(Action::Back | Action::Transcript) => self.mode = Mode::Normal,
+            Some(Action::Down) => self.scroll_transcript(1),
+            Some(Action::Up) => self.scroll_transcript(-1),
+            Some(Action::PageDown) => self.scroll_transcript(i32::from(page)),
+            Some(Action::PageUp) => self.scroll_transcript(-i32::from(page)),
+            Some(Action::Top) => {
+                if let Some(view) = self.transcript.as_mut() {
+                    view.scroll = 0;
+                }
+            }
+            Some(Action::Bottom) => {
+                let rows = self.wrapped_rows();
+                if let Some(view) = self.transcript.as_mut() {
+                    view.scroll = rows.saturating_sub(page);
+                }
+            }
+            _ => {}
+        }
+        Ok(AppAction::None)
+    }
+
+    fn scroll_transcript(&mut self, delta: i32) {
+        let max = i32::from(self.wrapped_rows()) - 1;
+        if let Some(view) = self.transcript.as_mut() {
+            let next = (i32::from(view.scroll) + delta).clamp(0, max.max(0));
+            view.scroll = u16::try_from(next).unwrap_or(u16::MAX);
+        }
+    }
+
+    /// Screen rows of the transcript, falling back to its line count before the first draw.
+    fn wrapped_rows(&self) -> u16 {
+        let lines = self.transcript.as_ref().map_or(0, |view| {
+            u16::try_from(view.lines.len()).unwrap_or(u16::MAX)
+        });
+        self.transcript_rows.get().max(lines)
+    }
+
+    /// Load the highlighted session's transcript unless it is already cached.
+    fn sync_transcript(&mut self) {
+        let Some(session) = self.current_session() else {
+            self.transcript = None;
+            return;
+        };
+        if self
+            .transcript
+            .as_ref()
+            .is_some_and(|view| view.path == session.path)
+        {
+            return;
+        }
+        let path = session.path.clone();
+        let opts = ExportOptions {
+            kinds: EntryKind::ALL.to_vec(),
+            max_output_lines: Some(TRANSCRIPT_MAX_OUTPUT_LINES),
+            title: None,
+        };
+        let lines = match read_session_entries(&path, &opts) {
+            Ok((_, entries)) => render_transcript(&entries),
+            Err(err) => vec![Line::from(format!("Failed to load transcript: {err}"))],
+        };
+        self.transcript = Some(TranscriptView {
+            path,
+            lines,
+            scroll: 0,
+        });
+        self.transcript_rows.set(0);
+    }
+
+    fn handle_fulltext_input(&mut self, key: KeyEvent) -> Result<AppAction> {
         match key.code {
-            KeyCode::Esc | KeyCode::Char('n') => {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Enter => {
+                self.run_fulltext_search();
+            }
+            KeyCode::Backspace => {
+                self.fulltext_query.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.fulltext_query.push(c);
+            }
+            _ => {}
+        }
+        Ok(AppAction::None)
+    }
+
+    fn handle_fulltext_results(&mut self, key: KeyEvent) -> Result<AppAction> {
+        match self.keymap.action(&key, &Action::RESULTS) {
+            Some(Action::Back) => self.mode = Mode::Normal,
+            Some(Action::Up) => {
+                self.fulltext_selected = self.fulltext_selected.saturating_sub(1);
+            }
+            Some(Action::Down) if self.fulltext_selected + 1 < self.fulltext_hits.len() => {
+                self.fulltext_selected += 1;
+            }
+            Some(Action::Open) => {
+                if let Some(hit) = self.fulltext_hits.get(self.fulltext_selected) {
+                    let session_idx = hit.session_idx;
+                    self.select_session(session_idx);
+                }
                 self.mode = Mode::Normal;
             }
-            KeyCode::Char('r') => {
+            _ => {}
+        }
+        Ok(AppAction::None)
+    }
+
+    fn run_fulltext_search(&mut self) {
+        let query = self.fulltext_query.trim().to_string();
+        if query.is_empty() {
+            self.mode = Mode::Normal;
+            return;
+        }
+        self.fulltext_hits = self
+            .sessions
+            .iter()
+            .enumerate()
+            .filter_map(|(session_idx, session)| {
+                search_transcript(&session.path, &query, FULLTEXT_SNIPPETS)
+                    .ok()
+                    .filter(|snippets| !snippets.is_empty())
+                    .map(|snippets| FullTextHit {
+                        session_idx,
+                        snippets,
+                    })
+            })
+            .collect();
+        self.fulltext_selected = 0;
+        if self.fulltext_hits.is_empty() {
+            self.status = Some(format!("No transcripts mention \"{query}\""));
+            self.mode = Mode::Normal;
+        } else {
+            self.mode = Mode::FullTextResults;
+        }
+    }
+
+    fn cycle_sort(&mut self) {
+        let current = self.current_session().map(|session| session.path.clone());
+        self.sort_key = self.sort_key.next();
+        sort_sessions(&mut self.sessions, self.sort_key);
+        self.apply_filter();
+        if let Some(path) = current
+            && let Some(idx) = self.sessions.iter().position(|s| s.path == path)
+        {
+            self.select_session(idx);
+        }
+        self.status = Some(format!("Sorted by {}", self.sort_key));
+        self.save_view();
+    }
+
+    fn toggle_grouping(&mut self) {
+        let current = self.current_session().map(|session| session.path.clone());
+        self.group_by_repo = !self.group_by_repo;
+        self.apply_filter();
+        if let Some(path) = current
+            && let Some(idx) = self.sessions.iter().position(|s| s.path == path)
+        {
+            self.select_session(idx);
+        }
+        self.status = Some(String::from(if self.group_by_repo {
+            "Grouped by repository"
+        } else {
+            "Ungrouped"
+        }));
+        self.save_view();
+    }
+
+    fn toggle_scope(&mut self) {
+        let current = self.current_session().map(|session| session.path.clone());
+        self.scoped = !self.scoped;
+        self.apply_filter();
+        if let Some(path) = current
+            && let Some(pos) = self
+                .filtered
+                .iter()
+                .position(|&idx| self.sessions[idx].path == path)
+        {
+            self.selected = pos;
+        }
+        self.status = Some(format!("Showing {}", self.scope_label()));
+    }
+
+    fn scope_label(&self) -> String {
+        if self.scoped {
+            self.project.describe()
+        } else {
+            CwdScope::All.describe()
+        }
+    }
+
+    fn grouping_hint(&self) -> &'static str {
+        if self.group_by_repo {
+            " · grouped by repository"
+        } else {
+            ""
+        }
+    }
+
+    /// Check the git state of the rows on screen, once per session.
+    fn check_git(&mut self) {
+        let visible = usize::from(self.table_rows.get());
+        for &idx in self.filtered.iter().take(visible) {
+            let summary = &mut self.sessions[idx];
+            if self.git_checked.insert(summary.path.clone()) {
+                summary.git_status = self.git.status(summary);
+            }
+        }
+    }
+
+    fn handle_columns_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
+        match self.keymap.action(&key, &Action::COLUMNS) {
+            Some(Action::Back | Action::Columns) => self.mode = Mode::Normal,
+            Some(Action::Up) => {
+                self.column_cursor = self.column_cursor.saturating_sub(1);
+            }
+            Some(Action::Down) if self.column_cursor + 1 < Column::ALL.len() => {
+                self.column_cursor += 1;
+            }
+            Some(Action::Toggle) => {
+                let column = Column::ALL[self.column_cursor];
+                if self.columns.contains(&column) {
+                    self.columns.retain(|shown| *shown != column);
+                } else {
+                    // Keep the picker's order so toggling back restores the original layout.
+                    self.columns.push(column);
+                    self.columns.sort_by_key(|shown| {
+                        Column::ALL.iter().position(|candidate| candidate == shown)
+                    });
+                }
+                self.save_view();
+            }
+            _ => {}
+        }
+        Ok(AppAction::None)
+    }
+
+    /// Remember the sort order and columns in the config file for the next launch.
+    fn save_view(&mut self) {
+        let view = ViewConfig {
+            sort: self.sort_key,
+            columns: self.columns.clone(),
+            group_by_repo: self.group_by_repo,
+        };
+        if let Err(err) = config::save_view(&self.config_path, &view) {
+            self.status = Some(format!("Could not save view settings: {err:#}"));
+        }
+    }
+
+    /// Move the table cursor onto a session, clearing the filter if it hides that row.
+    fn select_session(&mut self, session_idx: usize) {
+        if !self.filtered.contains(&session_idx) {
+            self.query.clear();
+            self.apply_filter();
+        }
+        if let Some(pos) = self.filtered.iter().position(|&idx| idx == session_idx) {
+            self.selected = pos;
+        }
+    }
+
+    fn handle_action_prompt(&mut self, key: KeyEvent) -> Result<AppAction> {
+        match self.keymap.action(&key, &Action::PROMPT) {
+            Some(Action::Back) => self.mode = Mode::Normal,
+            Some(Action::Resume) => {
                 if let Some(session) = self.current_session().cloned() {
                     self.mode = Mode::Normal;
-                    return Ok(AppAction::Resume(session));
+                    let command = self.launcher.command(&session);
+                    return Ok(AppAction::Resume(session, command));
+                }
+            }
+            Some(Action::Edit) => self.start_edit_resume(),
+            Some(Action::ResumeOriginal) => {
+                if let Some(session) = self.current_session().cloned()
+                    && let Some(original) = self.original.as_ref()
+                {
+                    self.mode = Mode::Normal;
+                    let command = self.launcher.command_matching(&session, original);
+                    return Ok(AppAction::Resume(session, command));
                 }
             }
-            KeyCode::Char('j') => {
+            Some(Action::Jump) => {
                 if let Some(session) = self.current_session().cloned() {
                     if session.cwd.is_some() {
                         self.mode = Mode::Normal;
-                        return Ok(AppAction::Jump(session));
+                        let command = self.launcher.command(&session);
+                        return Ok(AppAction::Jump(session, command));
                     }
                     self.status = Some(String::from("No CWD recorded for this session"));
                     self.mode = Mode::Normal;
@@ -387,6 +1166,74 @@ impl App {
         Ok(AppAction::None)
     }
 
+    /// Prefill the resume command line of the highlighted session for editing.
+    fn start_edit_resume(&mut self) {
+        let Some(session) = self.current_session() else {
+            self.mode = Mode::Normal;
+            return;
+        };
+        self.resume_line = self.launcher.command(session).to_shell();
+        self.resume_cursor = self.resume_line.chars().count();
+        self.mode = Mode::EditResume;
+    }
+
+    /// Byte offset of the char at `resume_cursor`.
+    fn resume_offset(&self, cursor: usize) -> usize {
+        self.resume_line
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.resume_line.len(), |(offset, _)| offset)
+    }
+
+    fn handle_edit_resume(&mut self, key: KeyEvent) -> Result<AppAction> {
+        match key.code {
+            KeyCode::Esc => {
+                self.resume_line.clear();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Enter => {
+                let Some(session) = self.current_session().cloned() else {
+                    self.mode = Mode::Normal;
+                    return Ok(AppAction::None);
+                };
+                match ResumeCommand::parse(&self.resume_line) {
+                    Ok(command) => {
+                        self.mode = Mode::Normal;
+                        return Ok(AppAction::Resume(session, command));
+                    }
+                    // Stay in the editor so the line can be fixed.
+                    Err(err) => self.status = Some(format!("{err:#}")),
+                }
+            }
+            KeyCode::Left => self.resume_cursor = self.resume_cursor.saturating_sub(1),
+            KeyCode::Right => {
+                self.resume_cursor = (self.resume_cursor + 1).min(self.resume_line.chars().count());
+            }
+            KeyCode::Home => self.resume_cursor = 0,
+            KeyCode::End => self.resume_cursor = self.resume_line.chars().count(),
+            KeyCode::Backspace if self.resume_cursor > 0 => {
+                self.resume_cursor -= 1;
+                let offset = self.resume_offset(self.resume_cursor);
+                self.resume_line.remove(offset);
+            }
+            KeyCode::Delete if self.resume_cursor < self.resume_line.chars().count() => {
+                let offset = self.resume_offset(self.resume_cursor);
+                self.resume_line.remove(offset);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.resume_line.clear();
+                self.resume_cursor = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let offset = self.resume_offset(self.resume_cursor);
+                self.resume_line.insert(offset, c);
+                self.resume_cursor += 1;
+            }
+            _ => {}
+        }
+        Ok(AppAction::None)
+    }
+
     fn handle_command_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
         match key.code {
             KeyCode::Esc => {
@@ -402,10 +1249,8 @@ impl App {
             KeyCode::Backspace => {
                 self.command.pop();
             }
-            KeyCode::Char(c) => {
-                if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.command.push(c);
-                }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.command.push(c);
             }
             _ => {}
         }
@@ -415,13 +1260,7 @@ impl App {
     fn handle_confirm_mode(&mut self, key: KeyEvent) -> Result<AppAction> {
         match key.code {
             KeyCode::Char('y') => {
-                if let Some(session) = self.current_session().cloned() {
-                    std::fs::remove_file(&session.path)
-                        .with_context(|| format!("failed to delete {:?}", session.path))?;
-                    self.sessions.retain(|s| s.path != session.path);
-                    self.apply_filter();
-                    self.status = Some(format!("Deleted session {}", session.id));
-                }
+                self.delete_targets();
                 self.mode = Mode::Normal;
             }
             KeyCode::Char('n') | KeyCode::Esc => {
@@ -432,6 +1271,250 @@ impl App {
         Ok(AppAction::None)
     }
 
+    fn delete_targets(&mut self) {
+        let mut deleted = Vec::new();
+        let mut failures = Vec::new();
+        for session in self.targets() {
+            match trash::trash_session(&self.codex_home, &session.path) {
+                Ok(entry) => deleted.push((entry, session)),
+                Err(err) => failures.push(format!("{}: {err:#}", session.id)),
+            }
+        }
+        let done: Vec<&SessionSummary> = deleted.iter().map(|(_, session)| session).collect();
+        self.forget_sessions(&done);
+        self.status = Some(outcome_status(
+            &done,
+            "Moved",
+            "to the trash (u to undo)",
+            &failures,
+        ));
+        if !deleted.is_empty() {
+            self.last_deleted = deleted;
+        }
+    }
+
+    fn archive_targets(&mut self) {
+        let mut archived = Vec::new();
+        let mut failures = Vec::new();
+        for session in self.targets() {
+            match archive_session(&self.codex_home, &session.path) {
+                Ok(_) => archived.push(session),
+                Err(err) => failures.push(format!("{}: {err:#}", session.id)),
+            }
+        }
+        let done: Vec<&SessionSummary> = archived.iter().collect();
+        self.forget_sessions(&done);
+        self.status = Some(outcome_status(
+            &done,
+            "Archived",
+            "(restore with `codex-session unarchive`)",
+            &failures,
+        ));
+    }
+
+    /// Drop sessions that no longer live in the sessions directory from the list and selection.
+    fn forget_sessions(&mut self, gone: &[&SessionSummary]) {
+        for session in gone {
+            self.marked.remove(&session.path);
+        }
+        self.sessions
+            .retain(|s| !gone.iter().any(|session| session.path == s.path));
+        self.apply_filter();
+    }
+
+    fn undo_delete(&mut self) {
+        if self.last_deleted.is_empty() {
+            self.status = Some(String::from("Nothing to undo"));
+            return;
+        }
+        let mut restored = Vec::new();
+        let mut failures = Vec::new();
+        for (entry, session) in std::mem::take(&mut self.last_deleted) {
+            match trash::restore(&entry) {
+                Ok(_) => restored.push(session),
+                Err(err) => failures.push(format!("{}: {err:#}", session.id)),
+            }
+        }
+        let first = restored.first().map(|session| session.path.clone());
+        self.status = Some(outcome_status(
+            &restored.iter().collect::<Vec<_>>(),
+            "Restored",
+            "",
+            &failures,
+        ));
+        self.sessions.extend(restored);
+        sort_sessions(&mut self.sessions, self.sort_key);
+        self.apply_filter();
+        if let Some(path) = first
+            && let Some(idx) = self.sessions.iter().position(|s| s.path == path)
+        {
+            self.select_session(idx);
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(path) = self.current_session().map(|session| session.path.clone()) else {
+            return;
+        };
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+        self.move_selection_down();
+    }
+
+    /// Start a range at the cursor, or mark every row between the anchor and the cursor.
+    fn mark_range(&mut self) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let Some(anchor) = self.range_anchor.take() else {
+            self.range_anchor = Some(self.selected);
+            return;
+        };
+        let (start, end) = (anchor.min(self.selected), anchor.max(self.selected));
+        for &idx in &self.filtered[start..=end.min(self.filtered.len() - 1)] {
+            self.marked.insert(self.sessions[idx].path.clone());
+        }
+    }
+
+    /// Mark every visible row, or clear them all when they are already marked.
+    fn mark_all_filtered(&mut self) {
+        let visible: Vec<PathBuf> = self
+            .filtered
+            .iter()
+            .map(|&idx| self.sessions[idx].path.clone())
+            .collect();
+        if visible.iter().all(|path| self.marked.contains(path)) {
+            for path in &visible {
+                self.marked.remove(path);
+            }
+        } else {
+            self.marked.extend(visible);
+        }
+    }
+
+    /// Add (or remove) the space-separated tags in `args` on every targeted session.
+    fn retag(&mut self, args: &str, add: bool) -> Result<()> {
+        let tags: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        if tags.is_empty() {
+            self.status = Some(String::from(if add {
+                "usage: :tag <tag>..."
+            } else {
+                "usage: :untag <tag>..."
+            }));
+            return Ok(());
+        }
+        let targets = self.targets();
+        let mut store = match AnnotationStore::load(&self.codex_home) {
+            Ok(store) => store,
+            Err(err) => {
+                self.status = Some(format!("Tagging failed: {err:#}"));
+                return Ok(());
+            }
+        };
+        for session in &targets {
+            if add {
+                store.add_tags(&session.id, &tags);
+            } else {
+                store.remove_tags(&session.id, &tags);
+            }
+        }
+        if let Err(err) = store.save() {
+            self.status = Some(format!("Tagging failed: {err:#}"));
+            return Ok(());
+        }
+        for summary in &mut self.sessions {
+            if targets.iter().any(|session| session.path == summary.path) {
+                summary.tags = store
+                    .get(&summary.id)
+                    .map(|entry| entry.tags.clone())
+                    .unwrap_or_default();
+            }
+        }
+        self.apply_filter();
+        let done: Vec<&SessionSummary> = targets.iter().collect();
+        let verb = if add { "Tagged" } else { "Untagged" };
+        self.status = Some(outcome_status(&done, verb, "", &[]));
+        Ok(())
+    }
+
+    /// Set the highlighted session's title; an empty title restores the preview.
+    fn retitle(&mut self, text: &str) -> Result<()> {
+        let Some(session) = self.current_session().cloned() else {
+            return Ok(());
+        };
+        let title = Some(text.trim().to_string()).filter(|title| !title.is_empty());
+        let mut store = match AnnotationStore::load(&self.codex_home) {
+            Ok(store) => store,
+            Err(err) => {
+                self.status = Some(format!("Renaming failed: {err:#}"));
+                return Ok(());
+            }
+        };
+        store.set_title(&session.id, title.clone());
+        if let Err(err) = store.save() {
+            self.status = Some(format!("Renaming failed: {err:#}"));
+            return Ok(());
+        }
+        if let Some(summary) = self.sessions.iter_mut().find(|s| s.path == session.path) {
+            summary.title = title;
+        }
+        self.status = Some(format!("Updated the title of {}", session.id));
+        Ok(())
+    }
+
+    /// Pin the targeted sessions, or unpin them when every one is already pinned.
+    fn toggle_pin(&mut self) -> Result<()> {
+        let targets = self.targets();
+        if targets.is_empty() {
+            return Ok(());
+        }
+        let pinned = !targets.iter().all(|session| session.pinned);
+        let mut store = match AnnotationStore::load(&self.codex_home) {
+            Ok(store) => store,
+            Err(err) => {
+                self.status = Some(format!("Pinning failed: {err:#}"));
+                return Ok(());
+            }
+        };
+        for session in &targets {
+            store.set_pinned(&session.id, pinned);
+        }
+        if let Err(err) = store.save() {
+            self.status = Some(format!("Pinning failed: {err:#}"));
+            return Ok(());
+        }
+        let current = self.current_session().map(|session| session.path.clone());
+        for summary in &mut self.sessions {
+            if targets.iter().any(|session| session.path == summary.path) {
+                summary.pinned = pinned;
+            }
+        }
+        sort_sessions(&mut self.sessions, self.sort_key);
+        self.apply_filter();
+        if let Some(path) = current
+            && let Some(idx) = self.sessions.iter().position(|s| s.path == path)
+        {
+            self.select_session(idx);
+        }
+        let done: Vec<&SessionSummary> = targets.iter().collect();
+        let verb = if pinned { "Pinned" } else { "Unpinned" };
+        self.status = Some(outcome_status(&done, verb, "", &[]));
+        Ok(())
+    }
+
+    /// Sessions an action applies to: the multi-selection, or the highlighted row without one.
+    fn targets(&self) -> Vec<SessionSummary> {
+        if self.marked.is_empty() {
+            return self.current_session().cloned().into_iter().collect();
+        }
+        self.sessions
+            .iter()
+            .filter(|session| self.marked.contains(&session.path))
+            .cloned()
+            .collect()
+    }
+
     fn move_selection_up(&mut self) {
         if self.filtered.is_empty() {
             return;
@@ -460,13 +1543,38 @@ impl App {
         if command.is_empty() {
             return Ok(());
         }
-        if let Some(rest) = command.strip_prefix("export") {
-            let path = rest.trim();
+        let (word, rest) = command
+            .split_once(char::is_whitespace)
+            .unwrap_or((command, ""));
+        if word == "export" {
+            let (mut opts, path) = match parse_export_args(rest) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    self.status = Some(err);
+                    return Ok(());
+                }
+            };
             if path.is_empty() {
-                self.status = Some(String::from("usage: :export <file_path>"));
-            } else if let Some(session) = self.current_session() {
-                let dest = PathBuf::from(path);
-                match export_session_chat(&session.path, &dest) {
+                self.status = Some(String::from(
+                    "usage: :export [--include KINDS] [--max-output-lines N] <file_path>",
+                ));
+                return Ok(());
+            }
+            let targets = self.targets();
+            if targets.is_empty() {
+                return Ok(());
+            }
+            let dest = self.export.resolve(Path::new(path));
+            if let Some(parent) = dest.parent()
+                && !parent.as_os_str().is_empty()
+                && let Err(err) = std::fs::create_dir_all(parent)
+            {
+                self.status = Some(format!("Export failed: {err}"));
+                return Ok(());
+            }
+            if let [session] = targets.as_slice() {
+                opts.title = session.title.clone();
+                match export_session_chat(&session.path, &dest, &opts) {
                     Ok(_) => {
                         self.status =
                             Some(format!("Exported {} to {}", session.id, dest.display()));
@@ -475,7 +1583,30 @@ impl App {
                         self.status = Some(format!("Export failed: {err}"));
                     }
                 }
+                return Ok(());
+            }
+            let mut exported = Vec::new();
+            let mut failures = Vec::new();
+            for session in &targets {
+                let target = per_session_path(&dest, &session.id);
+                opts.title = session.title.clone();
+                match export_session_chat(&session.path, &target, &opts) {
+                    Ok(_) => exported.push(session),
+                    Err(err) => failures.push(format!("{}: {err}", session.id)),
+                }
             }
+            self.status = Some(outcome_status(
+                &exported,
+                "Exported",
+                &format!("next to {}", dest.display()),
+                &failures,
+            ));
+        } else if word == "title" {
+            self.retitle(rest)?;
+        } else if word == "untag" {
+            self.retag(rest, false)?;
+        } else if word == "tag" {
+            self.retag(rest, true)?;
         } else {
             self.status = Some(format!("Unknown command: {command}"));
         }
@@ -483,6 +1614,74 @@ impl App {
     }
 }
 
+/// Parse leading `--include` / `--max-output-lines` flags; the remainder is the target path.
+fn parse_export_args(args: &str) -> Result<(ExportOptions, &str), String> {
+    let mut opts = ExportOptions::default();
+    let mut rest = args.trim();
+    while let Some(flagged) = rest.strip_prefix("--") {
+        let (flag, after) = flagged.split_once(' ').unwrap_or((flagged, ""));
+        let (value, after) = after.trim_start().split_once(' ').unwrap_or((after, ""));
+        match flag {
+            "include" => {
+                opts.kinds = value
+                    .split(',')
+                    .filter(|kind| !kind.trim().is_empty())
+                    .map(str::parse::<EntryKind>)
+                    .collect::<Result<_, _>>()?;
+            }
+            "max-output-lines" => {
+                let max = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid --max-output-lines value: {value}"))?;
+                opts.max_output_lines = (max > 0).then_some(max);
+            }
+            other => return Err(format!("unknown export option --{other}")),
+        }
+        rest = after.trim_start();
+    }
+    Ok((opts, rest))
+}
+
+/// `notes.md` becomes `notes-<id>.md` so a batch export writes one file per session.
+fn per_session_path(dest: &Path, id: &str) -> PathBuf {
+    let stem = dest
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match dest.extension() {
+        Some(ext) => format!("{stem}-{id}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{id}"),
+    };
+    dest.with_file_name(name)
+}
+
+/// One status line covering a batch: what succeeded, then each failure with its reason.
+fn outcome_status(
+    done: &[&SessionSummary],
+    verb: &str,
+    suffix: &str,
+    failures: &[String],
+) -> String {
+    let mut status = match done {
+        [] => String::new(),
+        [session] => format!("{verb} session {} {suffix}", session.id),
+        _ => format!("{verb} {} sessions {suffix}", done.len()),
+    };
+    let status_len = status.trim_end().len();
+    status.truncate(status_len);
+    if !failures.is_empty() {
+        if !status.is_empty() {
+            status.push_str(" · ");
+        }
+        status.push_str(&format!(
+            "{} failed: {}",
+            failures.len(),
+            failures.join("; ")
+        ));
+    }
+    status
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::vertical([
         Constraint::Percentage((100 - percent_y) / 2),